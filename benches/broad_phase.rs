@@ -0,0 +1,113 @@
+//Compares the broad phase grid against testing every collider, run with `cargo bench`
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use macroquad::prelude::*;
+use macroquad_experiment::{broad_phase::broad_phase::UniformGrid, game_engine::{BROAD_PHASE_CELL_SIZE, DEFAULT_TABLE, PHYSICS_TARGET_FRAMETIME}, physics_obj::physics_obj::PhysicsBody, static_obj::static_obj::StaticBody, table::table::TableDescription};
+
+const BALLS: usize = 64;
+const STEPS: usize = 1000;
+
+//Small deterministic generator so both runs see the same balls
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn default_table() -> Vec<StaticBody> {
+    TableDescription::from_file(DEFAULT_TABLE)
+        .and_then(|description| description.build())
+        .expect("Failed to load default table")
+        .colliders
+        .into_bodies()
+}
+
+//Default table with a large field of bumpers and posts below it
+fn large_table() -> Vec<StaticBody> {
+    let mut colliders = default_table();
+    for y in 0..40 {
+        for x in 0..40 {
+            let position = vec2(x as f32 * 50.0, 800.0 + y as f32 * 50.0);
+            if (x + y) % 2 == 0 {
+                colliders.push(StaticBody::new_circle(position, 8.0, WHITE, 0.0));
+            } else {
+                colliders.push(StaticBody::new_rectangle(position, vec2(20.0, 6.0), 0.4, GRAY, 0.0));
+            }
+        }
+    }
+    colliders
+}
+
+//Balls scattered over `area` with random velocities, the same for every run
+fn balls(area: Rect) -> Vec<PhysicsBody> {
+    let mut rng = Lcg(42);
+    (0..BALLS)
+        .map(|_| PhysicsBody::new(
+            vec2(area.x + rng.next() * area.w, area.y + rng.next() * area.h),
+            vec2(rng.next() - 0.5, rng.next() - 0.5) * 1600.0,
+            10.0,
+        ))
+        .collect()
+}
+
+//Narrow phase of one ball against one collider, what update_physics runs for every candidate
+fn narrow_phase(collider: &mut StaticBody, ball: &PhysicsBody, motion: Vec2) -> usize {
+    collider.time_of_impact(ball.position, motion, ball.radius).is_some() as usize
+        + collider.collision_check(ball).is_some() as usize
+}
+
+//Moves every ball along its velocity, wrapping around `area` so they stay among the colliders.
+//`test` runs the collision tests for a ball and returns how many hit.
+fn run(area: Rect, mut test: impl FnMut(&PhysicsBody, Vec2) -> usize) -> Duration {
+    let mut balls = balls(area);
+    let mut hits = 0;
+
+    let start = Instant::now();
+    for _ in 0..STEPS {
+        for ball in &mut balls {
+            let motion = ball.velocity * PHYSICS_TARGET_FRAMETIME;
+            hits += test(ball, motion);
+            ball.position += motion;
+            ball.position.x = area.x + (ball.position.x - area.x).rem_euclid(area.w);
+            ball.position.y = area.y + (ball.position.y - area.y).rem_euclid(area.h);
+        }
+    }
+    black_box(hits);
+    start.elapsed()
+}
+
+//Every collider is tested against every ball
+fn run_linear(mut colliders: Vec<StaticBody>, area: Rect) -> Duration {
+    run(area, |ball, motion| {
+        (0..colliders.len()).map(|i| narrow_phase(&mut colliders[i], ball, motion)).sum()
+    })
+}
+
+//Only colliders in the cells along the ball's path are tested
+fn run_grid(mut colliders: Vec<StaticBody>, area: Rect) -> Duration {
+    let broad_phase = UniformGrid::build(&colliders, BROAD_PHASE_CELL_SIZE);
+    let mut candidates = Vec::new();
+    run(area, |ball, motion| {
+        broad_phase.query(ball.swept_bounds(motion), &mut candidates);
+        candidates.iter().map(|&i| narrow_phase(&mut colliders[i], ball, motion)).sum()
+    })
+}
+
+//Each run gets its own freshly built colliders
+fn compare(name: &str, table: fn() -> Vec<StaticBody>, area: Rect) {
+    let linear_time = run_linear(table(), area);
+    let grid_time = run_grid(table(), area);
+    println!(
+        "{name:>8}: {:>5} colliders, linear {:>9.2?}, grid {:>9.2?}, speedup {:.2}x",
+        table().len(), linear_time, grid_time, linear_time.as_secs_f64() / grid_time.as_secs_f64()
+    );
+}
+
+fn main() {
+    println!("{BALLS} balls, {STEPS} steps");
+    compare("default", default_table, Rect::new(60.0, 60.0, 380.0, 500.0));
+    compare("large", large_table, Rect::new(0.0, 800.0, 2000.0, 2000.0));
+}
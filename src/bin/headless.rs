@@ -0,0 +1,103 @@
+//Runs the simulation without a window
+//Usage: headless [--table table.ron] [--seconds 60] [--record out.ron] [--replay in.ron]
+use std::{env, process::ExitCode};
+
+use macroquad_experiment::{game_engine::{game_engine::GameWorld, DEFAULT_TABLE, PHYSICS_TARGET_FRAMETIME}, helper::helper::arg_value, input::input::InputState, replay::replay::Replay};
+
+//Plunger is pulled for this long before the first release
+const LAUNCH_CHARGE_TIME: f32 = 2.0;
+//Flippers fire while the ball is below this height
+const AUTO_FLIP_Y: f32 = 560.0;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+
+    let replay = match arg_value(&args, "--replay") {
+        Some(path) => match Replay::from_file(&path) {
+            Ok(replay) => Some(replay),
+            Err(e) => {
+                eprintln!("Failed to load {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+    let seconds: f32 = match arg_value(&args, "--seconds").map(|s| s.parse()) {
+        Some(Ok(seconds)) => seconds,
+        Some(Err(e)) => {
+            eprintln!("Invalid duration: {e}");
+            return ExitCode::FAILURE;
+        },
+        None => 60.0,
+    };
+    let table_path = arg_value(&args, "--table")
+        .or_else(|| replay.as_ref().map(|r| r.table.clone()))
+        .unwrap_or_else(|| String::from(DEFAULT_TABLE));
+    let record_path = arg_value(&args, "--record");
+
+    let mut game = match GameWorld::load(&table_path) {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("Failed to load {table_path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if record_path.is_some() {
+        game.start_recording(&table_path);
+    }
+
+    let mut events = 0;
+    let steps = match &replay {
+        Some(replay) => {
+            for input in replay.inputs() {
+                game.physics_update(PHYSICS_TARGET_FRAMETIME, input);
+                events += game.take_events().len();
+            }
+            replay.len()
+        },
+        None => {
+            let steps = (seconds / PHYSICS_TARGET_FRAMETIME) as usize;
+            for step in 0..steps {
+                let time = step as f32 * PHYSICS_TARGET_FRAMETIME;
+                //Simple autoplay: pull and release the plunger once, then flip when the ball comes down
+                let flip = game.balls().iter().any(|b| b.position.y > AUTO_FLIP_Y);
+                let input = InputState {
+                    flippers: vec![flip; game.flipper_actions().len()],
+                    launch: time < LAUNCH_CHARGE_TIME,
+                    ..Default::default()
+                };
+                game.physics_update(PHYSICS_TARGET_FRAMETIME, &input);
+                events += game.take_events().len();
+            }
+            steps
+        }
+    };
+
+    println!("steps: {steps}");
+    println!("events: {events}");
+    println!("score: {}", game.score());
+    println!("lives: {}", game.lives());
+    for ball in game.balls() {
+        println!("ball: position ({:.2}, {:.2}) velocity ({:.2}, {:.2})", ball.position.x, ball.position.y, ball.velocity.x, ball.velocity.y);
+    }
+
+    if let (Some(path), Some(recording)) = (&record_path, game.finish_recording()) {
+        if let Err(e) = recording.save(path) {
+            eprintln!("Failed to save {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+        println!("recorded {} steps to {path}", recording.len());
+    }
+
+    //Playback has to end in exactly the recorded state
+    if let Some(expected) = replay.as_ref().and_then(|r| r.result.as_ref()) {
+        let result = game.replay_result();
+        if result != *expected {
+            eprintln!("replay diverged: expected {expected:?}, got {result:?}");
+            return ExitCode::FAILURE;
+        }
+        println!("replay matches recording");
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,187 @@
+pub mod broad_phase {
+    use macroquad::prelude::*;
+
+    use crate::static_obj::static_obj::StaticBody;
+
+    //Uniform grid over the colliders' bounding boxes, each cell lists the colliders touching it.
+    //Static bodies are inserted once, moving ones (flippers, spinners) are re-inserted every step.
+    //Bodies moved from outside the simulation need a refresh_all.
+    #[derive(Default)]
+    pub struct UniformGrid {
+        origin: Vec2,
+        cell_size: f32,
+        columns: usize,
+        rows: usize,
+        cells: Vec<Vec<usize>>,
+        //Cell range every collider is currently inserted into
+        ranges: Vec<(usize, usize, usize, usize)>,
+        //Indices of colliders that move on their own
+        moving: Vec<usize>,
+    }
+
+    impl UniformGrid {
+        pub fn build(colliders: &[StaticBody], cell_size: f32) -> UniformGrid {
+            let bounds = colliders.iter()
+                .map(|c| c.bounding_box())
+                .reduce(|a, b| a.combine_with(b))
+                .unwrap_or(Rect::new(0.0, 0.0, cell_size, cell_size));
+
+            let columns = ((bounds.w / cell_size).ceil() as usize).max(1);
+            let rows = ((bounds.h / cell_size).ceil() as usize).max(1);
+            let mut grid = UniformGrid {
+                origin: bounds.point(),
+                cell_size,
+                columns,
+                rows,
+                cells: vec![Vec::new(); columns * rows],
+                ranges: Vec::with_capacity(colliders.len()),
+                moving: Vec::new(),
+            };
+            grid.add_new(colliders);
+            grid
+        }
+
+        //Moves flippers, spinners and kinematic bodies to the cells matching their current bounding box
+        pub fn update_moving(&mut self, colliders: &[StaticBody]) {
+            for i in 0..self.moving.len() {
+                self.refresh(colliders, self.moving[i]);
+            }
+        }
+
+        //Re-inserts every collider whose bounding box changed and adds colliders pushed since the last call.
+        //Bodies outside the original bounds end up in the border cells, which queries clamp to as well.
+        pub fn refresh_all(&mut self, colliders: &[StaticBody]) {
+            for index in 0..self.ranges.len() {
+                self.refresh(colliders, index);
+            }
+            self.add_new(colliders);
+        }
+
+        fn refresh(&mut self, colliders: &[StaticBody], index: usize) {
+            let old_range = self.ranges[index];
+            let new_range = self.cell_range(colliders[index].bounding_box());
+            if new_range == old_range {
+                return;
+            }
+            self.for_each_cell(old_range, |cell| cell.retain(|c| *c != index));
+            self.insert(index, new_range);
+            self.ranges[index] = new_range;
+        }
+
+        fn add_new(&mut self, colliders: &[StaticBody]) {
+            for (index, collider) in colliders.iter().enumerate().skip(self.ranges.len()) {
+                let range = self.cell_range(collider.bounding_box());
+                self.insert(index, range);
+                self.ranges.push(range);
+                if collider.is_moving() {
+                    self.moving.push(index);
+                }
+            }
+        }
+
+        //Indices of every collider whose cells overlap `area`, sorted and without duplicates
+        pub fn query(&self, area: Rect, out: &mut Vec<usize>) {
+            out.clear();
+            let (x0, y0, x1, y1) = self.cell_range(area);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    out.extend_from_slice(&self.cells[y * self.columns + x]);
+                }
+            }
+            out.sort_unstable();
+            out.dedup();
+        }
+
+        fn insert(&mut self, index: usize, range: (usize, usize, usize, usize)) {
+            self.for_each_cell(range, |cell| cell.push(index));
+        }
+
+        fn for_each_cell(&mut self, (x0, y0, x1, y1): (usize, usize, usize, usize), mut f: impl FnMut(&mut Vec<usize>)) {
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    f(&mut self.cells[y * self.columns + x]);
+                }
+            }
+        }
+
+        //Inclusive (x0, y0, x1, y1) cells covered by `area`, clamped to the grid so nothing falls outside
+        fn cell_range(&self, area: Rect) -> (usize, usize, usize, usize) {
+            let cell = |value: f32, origin: f32, count: usize| {
+                (((value - origin) / self.cell_size).floor().max(0.0) as usize).min(count - 1)
+            };
+            (
+                cell(area.x, self.origin.x, self.columns),
+                cell(area.y, self.origin.y, self.rows),
+                cell(area.right(), self.origin.x, self.columns),
+                cell(area.bottom(), self.origin.y, self.rows),
+            )
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use macroquad::prelude::*;
+
+        use super::UniformGrid;
+        use crate::static_obj::static_obj::StaticBody;
+
+        //Posts, walls and a flipper spread over several cells, some of them spanning more than one
+        fn colliders() -> Vec<StaticBody> {
+            let mut colliders = vec![
+                StaticBody::new_rectangle(vec2(200.0, 10.0), vec2(400.0, 20.0), 0.0, GRAY, 0.0),
+                StaticBody::new_segment(vec2(0.0, 0.0), vec2(390.0, 500.0), 2.0, GRAY),
+                StaticBody::new_flipper(vec2(150.0, 450.0), vec2(30.0, 0.0), vec2(60.0, 10.0), 0.5, -0.5, GRAY),
+            ];
+            for i in 0..30 {
+                let position = vec2((i * 53 % 400) as f32, (i * 97 % 500) as f32);
+                colliders.push(StaticBody::new_circle(position, 5.0 + (i % 4) as f32 * 10.0, GRAY, 0.0));
+            }
+            colliders
+        }
+
+        //What a linear scan over every collider finds for `area`
+        fn linear_scan(colliders: &[StaticBody], area: Rect) -> Vec<usize> {
+            (0..colliders.len()).filter(|&i| colliders[i].bounding_box().overlaps(&area)).collect()
+        }
+
+        //Grid candidates that actually overlap `area`, cells are coarser than bounding boxes
+        fn grid_scan(grid: &UniformGrid, colliders: &[StaticBody], area: Rect) -> Vec<usize> {
+            let mut candidates = Vec::new();
+            grid.query(area, &mut candidates);
+            candidates.retain(|&i| colliders[i].bounding_box().overlaps(&area));
+            candidates
+        }
+
+        fn areas() -> impl Iterator<Item = Rect> {
+            (0..200).map(|i| Rect::new((i * 37 % 460) as f32 - 30.0, (i * 61 % 560) as f32 - 30.0, (i % 7) as f32 * 15.0, (i % 5) as f32 * 20.0))
+        }
+
+        #[test]
+        fn query_finds_what_a_linear_scan_finds() {
+            let colliders = colliders();
+            let grid = UniformGrid::build(&colliders, 64.0);
+            for area in areas() {
+                assert_eq!(grid_scan(&grid, &colliders, area), linear_scan(&colliders, area), "{area:?}");
+            }
+        }
+
+        #[test]
+        fn moved_and_added_colliders_are_found_after_updating() {
+            let mut colliders = colliders();
+            let mut grid = UniformGrid::build(&colliders, 64.0);
+
+            let StaticBody::Flipper { current_rotation, .. } = &mut colliders[2] else { unreachable!() };
+            *current_rotation = -0.5;
+            grid.update_moving(&colliders);
+            for area in areas() {
+                assert_eq!(grid_scan(&grid, &colliders, area), linear_scan(&colliders, area), "{area:?} after moving the flipper");
+            }
+
+            colliders[0] = StaticBody::new_rectangle(vec2(300.0, 300.0), vec2(40.0, 40.0), 0.3, GRAY, 0.0);
+            colliders.push(StaticBody::new_circle(vec2(120.0, 260.0), 25.0, GRAY, 0.0));
+            grid.refresh_all(&colliders);
+            for area in areas() {
+                assert_eq!(grid_scan(&grid, &colliders, area), linear_scan(&colliders, area), "{area:?} after refresh_all");
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+pub mod collider {
+    use std::collections::HashMap;
+
+    use crate::static_obj::static_obj::StaticBody;
+
+    //Identity of a collider that never changes while it exists, unlike its position in the list
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct ColliderId(pub u32);
+
+    #[derive(Clone, Debug)]
+    pub struct ColliderInfo {
+        pub id: ColliderId,
+        //Unique within a table
+        pub name: Option<String>,
+        pub tags: Vec<String>,
+    }
+
+    //Static bodies together with their ids, names and tags.
+    //Bodies stay in one contiguous slice so physics and the broad phase can index them directly.
+    #[derive(Default)]
+    pub struct ColliderSet {
+        bodies: Vec<StaticBody>,
+        info: Vec<ColliderInfo>,
+        //Playfield layer of every body, 0 is the main playfield, ramps are above it
+        layers: Vec<i32>,
+        by_id: HashMap<ColliderId, usize>,
+        by_name: HashMap<String, ColliderId>,
+        next_id: u32,
+    }
+
+    impl ColliderSet {
+        //Returns None if the name is already taken
+        pub fn push(&mut self, body: StaticBody, name: Option<String>, tags: Vec<String>, layer: i32) -> Option<ColliderId> {
+            if name.as_ref().is_some_and(|n| self.by_name.contains_key(n)) {
+                return None;
+            }
+
+            let id = ColliderId(self.next_id);
+            self.next_id += 1;
+
+            if let Some(name) = &name {
+                self.by_name.insert(name.clone(), id);
+            }
+            self.by_id.insert(id, self.bodies.len());
+            self.bodies.push(body);
+            self.info.push(ColliderInfo { id, name, tags });
+            self.layers.push(layer);
+            Some(id)
+        }
+
+        pub fn len(&self) -> usize {
+            self.bodies.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.bodies.is_empty()
+        }
+
+        pub fn bodies(&self) -> &[StaticBody] {
+            &self.bodies
+        }
+
+        pub fn bodies_mut(&mut self) -> &mut [StaticBody] {
+            &mut self.bodies
+        }
+
+        //Layer of every body, in the same order as bodies()
+        pub fn layers(&self) -> &[i32] {
+            &self.layers
+        }
+
+        //Bodies to update together with their layers
+        pub fn bodies_and_layers_mut(&mut self) -> (&mut [StaticBody], &[i32]) {
+            (&mut self.bodies, &self.layers)
+        }
+
+        pub fn layer(&self, id: ColliderId) -> Option<i32> {
+            self.by_id.get(&id).map(|&i| self.layers[i])
+        }
+
+        pub fn into_bodies(self) -> Vec<StaticBody> {
+            self.bodies
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (ColliderId, &StaticBody)> {
+            self.info.iter().map(|i| i.id).zip(self.bodies.iter())
+        }
+
+        pub fn iter_mut(&mut self) -> impl Iterator<Item = (ColliderId, &mut StaticBody)> {
+            self.info.iter().map(|i| i.id).zip(self.bodies.iter_mut())
+        }
+
+        pub fn get(&self, id: ColliderId) -> Option<&StaticBody> {
+            self.by_id.get(&id).map(|&i| &self.bodies[i])
+        }
+
+        pub fn get_mut(&mut self, id: ColliderId) -> Option<&mut StaticBody> {
+            self.by_id.get(&id).map(|&i| &mut self.bodies[i])
+        }
+
+        pub fn info(&self, id: ColliderId) -> Option<&ColliderInfo> {
+            self.by_id.get(&id).map(|&i| &self.info[i])
+        }
+
+        //Id of the body at a position in bodies()
+        pub fn id_at(&self, index: usize) -> ColliderId {
+            self.info[index].id
+        }
+
+        pub fn index_of(&self, id: ColliderId) -> Option<usize> {
+            self.by_id.get(&id).copied()
+        }
+
+        pub fn find(&self, name: &str) -> Option<ColliderId> {
+            self.by_name.get(name).copied()
+        }
+
+        //Bodies moved through this or bodies_mut need UniformGrid::refresh_all before they collide at their new place
+        pub fn find_mut(&mut self, name: &str) -> Option<&mut StaticBody> {
+            let id = self.find(name)?;
+            self.get_mut(id)
+        }
+
+        //Ids of every collider carrying `tag`, in table order
+        pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = ColliderId> + 'a {
+            self.info.iter().filter(move |i| i.tags.iter().any(|t| t == tag)).map(|i| i.id)
+        }
+
+        pub fn has_tag(&self, id: ColliderId, tag: &str) -> bool {
+            self.info(id).is_some_and(|i| i.tags.iter().any(|t| t == tag))
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use macroquad::prelude::*;
+
+        use super::ColliderSet;
+        use crate::static_obj::static_obj::StaticBody;
+
+        fn post(x: f32) -> StaticBody {
+            StaticBody::new_circle(vec2(x, 100.0), 10.0, GRAY, 0.0)
+        }
+
+        fn tags(tags: &[&str]) -> Vec<String> {
+            tags.iter().map(|t| t.to_string()).collect()
+        }
+
+        #[test]
+        fn bodies_are_found_by_name_and_tag() {
+            let mut colliders = ColliderSet::default();
+            let left = colliders.push(post(0.0), Some("left".into()), tags(&["bumper", "lit"]), 0).unwrap();
+            let plain = colliders.push(post(50.0), None, Vec::new(), 0).unwrap();
+            let right = colliders.push(post(100.0), Some("right".into()), tags(&["bumper"]), 1).unwrap();
+
+            assert_eq!(colliders.find("left"), Some(left));
+            assert_eq!(colliders.find("right"), Some(right));
+            assert_eq!(colliders.find("middle"), None);
+            assert!(matches!(colliders.find_mut("right"), Some(StaticBody::Circle { position, .. }) if position.x == 100.0));
+
+            assert_eq!(colliders.tagged("bumper").collect::<Vec<_>>(), [left, right]);
+            assert_eq!(colliders.tagged("lit").collect::<Vec<_>>(), [left]);
+            assert!(colliders.has_tag(left, "lit") && !colliders.has_tag(right, "lit") && !colliders.has_tag(plain, "bumper"));
+
+            assert_eq!(colliders.index_of(right), Some(2));
+            assert_eq!(colliders.id_at(1), plain);
+            assert_eq!(colliders.layer(right), Some(1));
+        }
+
+        #[test]
+        fn names_are_unique() {
+            let mut colliders = ColliderSet::default();
+            let first = colliders.push(post(0.0), Some("post".into()), Vec::new(), 0).unwrap();
+            assert_eq!(colliders.push(post(50.0), Some("post".into()), Vec::new(), 0), None);
+            assert_eq!(colliders.len(), 1);
+            assert_eq!(colliders.find("post"), Some(first));
+        }
+    }
+}
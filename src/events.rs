@@ -0,0 +1,48 @@
+pub mod events {
+    use macroquad::prelude::*;
+
+    use crate::{collider::collider::ColliderId, physics_obj::physics_obj::BallId};
+
+    //Everything noteworthy that happened during a physics step.
+    //Body ids are stable collider ids, ball ids are indices into the world's balls at the time of the event.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum GameEvent {
+        //Ball bounced off a body, impulse is the change of the ball's normal speed
+        BallHit { ball: BallId, body_id: ColliderId, point: Vec2, impulse: f32 },
+        //Spinner flap passed the top or bottom this many times
+        SpinnerRotation { body_id: ColliderId, half_turns: u32 },
+        BallDrained { ball: BallId },
+        //Plunger sent the ball off, speed is the ball's speed once the plunger is back at rest
+        BallLaunched { ball: BallId, speed: f32 },
+        //Ball drained during ball save and is served again
+        BallSaved,
+        //Flipper button went down
+        FlipperActivated { body_id: ColliderId },
+        //Pop bumper or slingshot kicked the ball
+        KickerFired { body_id: ColliderId },
+        //Drop target knocked down or standup target hit hard enough to count
+        TargetHit { body_id: ColliderId },
+        //Every target of a bank was hit, bank is an index into the table's banks
+        TargetBankCompleted { bank: usize, bonus: f32 },
+        //Ball centre moved into or out of a sensor
+        SensorEntered { ball: BallId, body_id: ColliderId },
+        SensorExited { ball: BallId, body_id: ColliderId },
+        //Ball crossed a layer gate onto a ramp or back down
+        LayerChanged { ball: BallId, body_id: ColliderId, layer: i32 },
+        //Saucer or ball lock caught the ball and holds it
+        BallCaptured { ball: BallId, body_id: ColliderId },
+        //Held ball was kicked back out
+        BallEjected { ball: BallId, body_id: ColliderId },
+        //Full ball lock let go of all its balls at once
+        MultiballStarted { body_id: ColliderId, balls: usize },
+        //Magnet or force zone switched on or off
+        FieldSwitched { body_id: ColliderId, enabled: bool },
+        //Extra ball was lit, or collected and put out
+        ExtraBallLit,
+        ExtraBallAwarded,
+    }
+
+    pub trait EventListener {
+        fn on_event(&mut self, event: &GameEvent);
+    }
+}
@@ -9,11 +9,25 @@ pub const PHYSICS_TARGET_FPS: f32 = 144.0;
 pub const PHYSICS_TARGET_FRAMETIME: f32 = 1.0 / PHYSICS_TARGET_FPS * PHYSICS_SPEED;
 //Cap to how many physics frames can happen in a game frame
 pub const MAX_PHYSICS_UPDATES_PER_FRAME: u32 = 10;
-//Flipper angular velocity
-pub const FLIPPER_SPEED: f32 = 7.5;
-//Launcher: maximum charge time length, maximum force
-pub const LAUNCHER_MAX_TIME: f32 = 2.0;
-pub const LAUNCHER_MAX_STRENGTH: f32 = 820.0;
+//Flippers bouncing off an end stop slower than this (radians per second) come to rest
+pub const FLIPPER_STOP_SPEED: f32 = 0.5;
+//Points for every half rotation of a spinner
+pub const SPINNER_HALF_TURN_SCORE: f32 = 100.0;
+//Points for every target hit, banks add their own bonus on top
+pub const TARGET_SCORE: f32 = 250.0;
+//Softer contacts (resting balls) don't count as target hits
+pub const TARGET_MIN_IMPULSE: f32 = 30.0;
+//Seconds before a completed target bank comes back up
+pub const TARGET_BANK_RESET_TIME: f32 = 1.0;
+//Seconds the launch button takes to pull the plunger all the way back, and the auto plunger's
+pub const PLUNGER_PULL_TIME: f32 = 2.0;
+pub const AUTO_PLUNGER_PULL_TIME: f32 = 0.5;
+//Most balls that can be on the table at once
+pub const MAX_BALLS: usize = 4;
+//Table loaded on startup
+pub const DEFAULT_TABLE: &str = "tables/default.ron";
+//Cell size of the broad phase grid
+pub const BROAD_PHASE_CELL_SIZE: f32 = 64.0;
 
 
 pub mod game_engine {
@@ -21,327 +35,1110 @@ pub mod game_engine {
 
     use macroquad::prelude::*;
 
-    use crate::{circle, curve, flipper, physics_obj::physics_obj::PhysicsBody, rect, static_obj::static_obj::StaticBody, helper::helper::format_number};
+    use crate::{broad_phase::broad_phase::UniformGrid, collider::collider::{ColliderId, ColliderSet}, events::events::{EventListener, GameEvent}, input::input::InputState, scoring::scoring::ScoreKeeper, physics_obj::physics_obj::{BallId, Capture, PhysicsBody}, replay::replay::{BallState, Replay, ReplayResult}, static_obj::static_obj::{StaticBody, SENSOR_LIT_TIME}, table::table::{FieldSwitch, Table, TableDescription, TableError, EXTRA_BALL_TAG, LIGHT_EXTRA_BALL_TAG}, targets::targets::TargetBank};
     use super::*;
 
+    //Pure simulation state, never touches the window so it can run headless
     #[derive(Default)]
     pub struct GameWorld {
-        ball: PhysicsBody,
+        pub(crate) balls: Vec<PhysicsBody>,
+        ball_radius: f32,
+        //Extra balls waiting for the spawn point to be free
+        pending_balls: usize,
+        //Id of the next ball served
+        next_ball_id: u32,
 
-        //colliders[0, 1] are (at least should be) flippers
-        colliders: Vec<StaticBody>,
+        pub(crate) colliders: ColliderSet,
+        broad_phase: UniformGrid,
+        //Bodies were handed out through colliders_mut and may have moved
+        colliders_changed: bool,
+        banks: Vec<TargetBank>,
+        field_switches: Vec<FieldSwitch>,
+        //Every distinct flipper action in table order, input has one entry per action
+        flipper_actions: Vec<String>,
+        //(flipper, index into flipper_actions) for every flipper on the table
+        flippers: Vec<(ColliderId, usize)>,
         physics_accumulated_time: f32,
 
-        launcher_accumulator: f32,
+        ball_spawn: Vec2,
+        drain_y: f32,
+        //Plunger driven by the launch button and the analog pull
+        pub(crate) launcher: ColliderId,
+        //Balls the plunger pushed since it was released, reported as launched once it is back at rest
+        launch_contacts: Vec<BallId>,
+        //Plunger pulls and fires by itself, for balls given back by ball save
+        auto_plunge: bool,
+        //Ball save length of the table, seconds left of it, and whether the next launch starts it
+        ball_save: f32,
+        ball_save_timer: f32,
+        ball_save_armed: bool,
+        //One-shot presses waiting for the next physics step
+        pending_restart: bool,
+        pending_reset: bool,
 
-        font: Option<Font>,
-        debug_draw_points: Vec<(Vec2, i32)>,
-        score: f32,
-        lives: u32,
+        //Every step's input is appended here while recording
+        recording: Option<Replay>,
+        //Inputs being played back instead of live input, and the next step to play
+        playback: Vec<InputState>,
+        playback_step: usize,
+
+        scoring: ScoreKeeper,
+        pub(crate) lives: u32,
+        //Next hit on an extra ball body awards a ball
+        extra_ball_lit: bool,
+        //Events waiting for take_events
+        events: Vec<GameEvent>,
+        //Flipper button state of the previous step, per action
+        flipper_held: Vec<bool>,
     }
 
     impl GameWorld {
-        pub async fn create() -> GameWorld {
-            let mut created_game = GameWorld::default();
+        pub fn create() -> GameWorld {
+            GameWorld::load(DEFAULT_TABLE).unwrap_or_else(|e| panic!("Failed to load {DEFAULT_TABLE}: {e}"))
+        }
+
+        pub fn load(table_path: &str) -> Result<GameWorld, TableError> {
+            let table = TableDescription::from_file(table_path)?.build()?;
+            Ok(GameWorld::new(table))
+        }
 
-            //Load font
-            created_game.font = Some(load_ttf_font("sans-medium.ttf").await.expect("No file"));
+        pub fn new(table: Table) -> GameWorld {
+            let mut created_game = GameWorld {
+                lives: 3,
+                ..Default::default()
+            };
+            created_game.load_table(table);
+
+            created_game
+        }
+
+        pub fn load_table(&mut self, table: Table) {
+            self.colliders = table.colliders;
+            self.broad_phase = UniformGrid::build(self.colliders.bodies(), BROAD_PHASE_CELL_SIZE);
+            self.colliders_changed = false;
+            self.scoring = ScoreKeeper::new(&self.colliders);
+            self.banks = table.banks;
+            self.field_switches = table.field_switches;
+            self.flipper_actions.clear();
+            self.flippers.clear();
+            for (id, action) in table.flippers {
+                let index = match self.flipper_actions.iter().position(|a| *a == action) {
+                    Some(index) => index,
+                    None => {
+                        self.flipper_actions.push(action);
+                        self.flipper_actions.len() - 1
+                    },
+                };
+                self.flippers.push((id, index));
+            }
+            self.flipper_held = vec![false; self.flipper_actions.len()];
+            self.ball_spawn = table.ball_spawn;
+            self.drain_y = table.drain_y;
+            self.launcher = table.launcher;
+            self.ball_save = table.ball_save;
+            self.ball_radius = table.ball_radius;
 
             //Create ball
-            created_game.ball = PhysicsBody::new(vec2(465.0, 600.0), vec2(0.0, 0.0),10.0);
-            created_game.score = 0.0;
-            created_game.lives = 3;
+            self.restart_ball();
+        }
 
-            created_game.initialize_world();
+        pub fn flipper_actions(&self) -> &[String] {
+            &self.flipper_actions
+        }
 
-            created_game
+        pub fn colliders(&self) -> &ColliderSet {
+            &self.colliders
         }
 
-        pub fn physics(&mut self) {
+        //For rules and scripts that move or retune bodies by name or tag.
+        //The broad phase is refreshed on the next step, so moved and added bodies collide where they are now.
+        pub fn colliders_mut(&mut self) -> &mut ColliderSet {
+            self.colliders_changed = true;
+            &mut self.colliders
+        }
+
+        pub fn balls(&self) -> &[PhysicsBody] {
+            &self.balls
+        }
+
+        pub fn score(&self) -> f32 {
+            self.scoring.score()
+        }
+
+        pub fn lives(&self) -> u32 {
+            self.lives
+        }
+
+        pub fn extra_ball_lit(&self) -> bool {
+            self.extra_ball_lit
+        }
+
+        pub fn start_recording(&mut self, table: &str) {
+            self.recording = Some(Replay::new(table));
+        }
+
+        //Stops recording and returns it along with the current state
+        pub fn finish_recording(&mut self) -> Option<Replay> {
+            let mut replay = self.recording.take()?;
+            replay.result = Some(self.replay_result());
+            Some(replay)
+        }
+
+        //Live input is ignored until every step of the replay has been played
+        pub fn play_replay(&mut self, replay: &Replay) {
+            self.playback = replay.inputs().cloned().collect();
+            self.playback_step = 0;
+        }
+
+        pub fn is_replaying(&self) -> bool {
+            self.playback_step < self.playback.len()
+        }
+
+        pub fn replay_result(&self) -> ReplayResult {
+            ReplayResult {
+                score: self.scoring.score(),
+                lives: self.lives,
+                balls: self.balls.iter()
+                    .map(|b| BallState { position: b.position.into(), velocity: b.velocity.into() })
+                    .collect(),
+            }
+        }
+
+        //Runs as many fixed steps as fit into frame_time
+        pub fn physics(&mut self, frame_time: f32, input: &InputState) {
             //Accumulated time
-            self.physics_accumulated_time += get_frame_time() * PHYSICS_SPEED;
+            self.physics_accumulated_time += frame_time * PHYSICS_SPEED;
+
+            //Presses are polled once per frame but must only be applied once
+            if !self.is_replaying() {
+                self.pending_restart |= input.restart;
+                self.pending_reset |= input.reset;
+            }
 
             let dt: f32 = PHYSICS_TARGET_FRAMETIME;
 
             let mut iteration = 0;
             while self.physics_accumulated_time > PHYSICS_TARGET_FRAMETIME && iteration < MAX_PHYSICS_UPDATES_PER_FRAME {
-                self.physics_update(dt);
+                let step_input = if self.is_replaying() {
+                    self.playback_step += 1;
+                    self.playback[self.playback_step - 1].clone()
+                } else {
+                    InputState {
+                        restart: std::mem::take(&mut self.pending_restart),
+                        reset: std::mem::take(&mut self.pending_reset),
+                        ..input.clone()
+                    }
+                };
+                self.physics_update(dt, &step_input);
                 
                 iteration += 1;
                 self.physics_accumulated_time -= PHYSICS_TARGET_FRAMETIME;
             }
         }
 
-        pub fn physics_update(&mut self, dt: f32) {
-            //Update flippers (index 0, 1)
-            self.update_flipper(dt, 0, KeyCode::Left);
-            self.update_flipper(dt, 1, KeyCode::Right);
+        //Single simulation step with explicit input and dt
+        pub fn physics_update(&mut self, dt: f32, input: &InputState) {
+            if let Some(recording) = &mut self.recording {
+                recording.push(input);
+            }
+            let first_event = self.events.len();
 
-            //Find all spinners
-            for s in self.colliders.iter_mut().filter(|e| matches!(e, StaticBody::Spinner { .. })) {
-                if let StaticBody::Spinner { acc_velocity, top_down_rotation, .. } = s {
-                    *top_down_rotation += *acc_velocity * dt;
-                    *acc_velocity = acc_velocity.signum() * f32::max(acc_velocity.abs() - dt * 5.0, 0.0);
-                    self.score += acc_velocity.abs() * dt * 100.0;
+            //Update flippers
+            for flipper in 0..self.flippers.len() {
+                let (id, action) = self.flippers[flipper];
+                let held = input.flipper(action);
+                if held && !self.flipper_held[action] {
+                    self.events.push(GameEvent::FlipperActivated { body_id: id });
                 }
+                self.update_flipper(dt, id, held);
+            }
+            for (action, held) in self.flipper_held.iter_mut().enumerate() {
+                *held = input.flipper(action);
             }
 
-            //If R pressed or ball is out of bounds, restart
-            if is_key_pressed(KeyCode::R) || self.ball.position.y > (screen_height() + 300.0) {
-                if self.lives > 0 {
-                    self.lives -= 1;
+            //Find all spinners
+            for (body_id, s) in self.colliders.iter_mut() {
+                if let StaticBody::Spinner { angular_velocity, top_down_rotation, friction, .. } = s {
+                    //Count every time the flap passes the top or the bottom
+                    let previous_rotation = *top_down_rotation;
+                    let rotation = previous_rotation + *angular_velocity * dt;
+                    let half_turns = ((rotation / PI).floor() - (previous_rotation / PI).floor()).abs() as u32;
+                    if half_turns > 0 {
+                        self.events.push(GameEvent::SpinnerRotation { body_id, half_turns });
+                    }
+
+                    *top_down_rotation = rotation.rem_euclid(2.0 * PI);
+                    *angular_velocity = angular_velocity.signum() * f32::max(angular_velocity.abs() - dt * *friction, 0.0);
+                }
+            }
 
-                    self.restart_ball();
+            for (body_id, body) in self.colliders.iter_mut() {
+                body.update_kicker(dt);
+                body.update_sensor(dt);
+                body.update_kinematic(dt);
+                if let Some(enabled) = body.field_mut().and_then(|f| f.update(dt)) {
+                    self.events.push(GameEvent::FieldSwitched { body_id, enabled });
                 }
             }
-            if is_key_pressed(KeyCode::E) {
+
+            //Drained balls leave the table, a life is only lost with the last one
+            for b in self.balls.iter().filter(|b| b.position.y > self.drain_y) {
+                self.events.push(GameEvent::BallDrained { ball: b.id });
+            }
+            let drain_y = self.drain_y;
+            self.balls.retain(|b| b.position.y <= drain_y);
+
+            //If R pressed or every ball is out of bounds or stuck in a lock, restart.
+            //Ball save gives the ball back for free and fires it automatically.
+            let out_of_play = self.pending_balls == 0 && self.balls.iter().all(|b| self.is_locked(b));
+            self.ball_save_timer = (self.ball_save_timer - dt).max(0.0);
+            if out_of_play && !input.restart && self.ball_save_timer > 0.0 {
+                self.events.push(GameEvent::BallSaved);
+                self.restart_ball();
+                self.ball_save_timer = 0.0;
+                self.ball_save_armed = false;
+                self.auto_plunge = true;
+            } else if (input.restart || out_of_play) && self.lives > 0 {
+                self.lives -= 1;
+
+                self.restart_ball();
+            }
+            if input.reset {
                 //Reset all spinners velociy
-                for s in self.colliders.iter_mut().filter(|e| matches!(e, StaticBody::Spinner { .. })) {
-                    if let StaticBody::Spinner { acc_velocity, .. } = s {
-                        *acc_velocity = 0.0;
+                for s in self.colliders.bodies_mut().iter_mut().filter(|e| matches!(e, StaticBody::Spinner { .. })) {
+                    if let StaticBody::Spinner { angular_velocity, .. } = s {
+                        *angular_velocity = 0.0;
                     }
                 }
 
+                for bank in &mut self.banks {
+                    bank.reset(&mut self.colliders);
+                }
+                for field in self.colliders.bodies_mut().iter_mut().filter_map(StaticBody::field_mut) {
+                    field.reset();
+                }
+
                 self.lives = 3;
-                self.score = 0.0;
+                self.extra_ball_lit = false;
+                self.scoring.reset();
                 self.restart_ball();
             }
 
-            if is_key_down(KeyCode::Space) {
-                //Add dt to launcher accumulator, while giving it a limit
-                self.launcher_accumulator = (self.launcher_accumulator + dt).clamp(0.0, LAUNCHER_MAX_TIME);
+            self.update_plunger(dt, input);
+
+            if self.colliders_changed {
+                self.broad_phase.refresh_all(self.colliders.bodies());
+                self.colliders_changed = false;
             }
-            if is_key_released(KeyCode::Space) 
-            {
-                if (460.0 < self.ball.position.x && self.ball.position.x < 470.0) && (595.0 < self.ball.position.y && self.ball.position.y < 605.0) {
-                    self.ball.velocity.y = -(self.launcher_accumulator/LAUNCHER_MAX_TIME) * LAUNCHER_MAX_STRENGTH;
+            self.broad_phase.update_moving(self.colliders.bodies());
+            let mut contacts = Vec::new();
+            for b in &mut self.balls {
+                let ball = b.id;
+                let (bodies, layers) = self.colliders.bodies_and_layers_mut();
+                b.update_physics(dt, bodies, layers, &self.broad_phase, &mut contacts);
+                for contact in contacts.drain(..) {
+                    let body_id = self.colliders.id_at(contact.body);
+                    if body_id == self.launcher
+                        && matches!(self.colliders.bodies()[contact.body], StaticBody::Plunger { speed, .. } if speed > 0.0)
+                        && !self.launch_contacts.contains(&ball) {
+                        self.launch_contacts.push(ball);
+                    }
+                    self.events.push(GameEvent::BallHit { ball, body_id, point: contact.point, impulse: contact.impulse });
+                    if contact.kicked {
+                        self.events.push(GameEvent::KickerFired { body_id });
+                    }
                 }
-                self.launcher_accumulator = 0.0;
             }
-            
-            self.ball.update_physics(dt, &mut self.colliders, &mut self.score, &mut self.debug_draw_points);
-        }
-
-        pub fn draw(&mut self) {
-            draw_circle(self.ball.position.x, self.ball.position.y, self.ball.radius, Color::from_rgba(190, 190, 200, 255));
 
-            //Render map
-            for obj in &self.colliders {
-                obj.draw();
+            //Ball to ball collisions
+            for i in 0..self.balls.len() {
+                let (current, rest) = self.balls[i..].split_first_mut().expect("Index is in range");
+                for other in rest {
+                    current.collide_with(other);
+                }
             }
 
-            //Render launcher
-            let launcher_percentage = self.launcher_accumulator / LAUNCHER_MAX_TIME;
-            draw_rectangle(460.0, 625.0, 10.0, (-1.0 + launcher_percentage * 0.9) * 15.0, YELLOW);
-
-            //Render score and lives
-            self.draw_number(format_number((self.score / 100.0) as i32 * 100), vec2(628.0, 25.0), 12.0, 25.0, 2.0);
-            self.draw_number(self.lives.to_string(), vec2(624.0, 100.0), 15.0, 30.0, 3.0);
+            self.update_sensors();
+            self.update_catchers(dt);
+            self.update_targets(dt, first_event);
 
-            //Draw debug points
-            for point in &mut self.debug_draw_points {
-                //draw_circle(point.0.x, point.0.y, 5.0, YELLOW);
-                point.1 -= 1;
+            //Hand this step's events to the game rules
+            let mut triggered = Vec::new();
+            let mut light_extra_ball = false;
+            let mut award_extra_ball = false;
+            for i in first_event..self.events.len() {
+                let event = &self.events[i];
+                self.scoring.on_event(event);
+                if let GameEvent::BallHit { body_id, .. } | GameEvent::SensorEntered { body_id, .. } | GameEvent::BallCaptured { body_id, .. } = event {
+                    triggered.extend(self.field_switches.iter().enumerate().filter(|(_, s)| s.trigger == *body_id).map(|(index, _)| index));
+                    light_extra_ball |= self.colliders.has_tag(*body_id, LIGHT_EXTRA_BALL_TAG);
+                }
+                if let GameEvent::BallHit { body_id, .. } | GameEvent::BallCaptured { body_id, .. } = event {
+                    award_extra_ball |= self.colliders.has_tag(*body_id, EXTRA_BALL_TAG);
+                }
             }
-
-            self.debug_draw_points.retain(|p| p.1 > 0);
+            //A lit extra ball is only awarded once, lighting it again takes another shot at a light
+            if award_extra_ball && self.extra_ball_lit {
+                self.extra_ball_lit = false;
+                self.add_ball();
+                self.events.push(GameEvent::ExtraBallAwarded);
+            } else if light_extra_ball && !self.extra_ball_lit {
+                self.extra_ball_lit = true;
+                self.events.push(GameEvent::ExtraBallLit);
+            }
+            for index in triggered {
+                let duration = self.field_switches[index].duration;
+                for field in self.field_switches[index].fields.clone() {
+                    self.switch_field(field, true, duration);
+                }
+            }
+            self.spawn_pending_ball();
         }
 
-        pub fn draw_number(&mut self, num: String, corner: Vec2, width: f32, height: f32, thickness: f32) {
-            let mut current_corner = corner;
-        
-            for c in num.chars().rev() {
-                match c {
-                    '0' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE); // top
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE); // bottom
-                        draw_rectangle(current_corner.x, current_corner.y, thickness, height, WHITE); // left
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE); // right
-                    }
-                    '1' => {
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
-                    }
-                    '2' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE); // top
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE); // middle
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE); // bottom
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height / 2.0, WHITE); // top right
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0, thickness, height / 2.0, WHITE); // bottom left
-                    }
-                    '3' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+        //Tracks which sensors every ball is in and moves balls crossing layer gates, reporting the changes
+        fn update_sensors(&mut self) {
+            for body in self.colliders.bodies_mut() {
+                if let StaticBody::Sensor { occupants, .. } = body {
+                    *occupants = 0;
+                }
+            }
+
+            let mut candidates = Vec::new();
+            for b in &mut self.balls {
+                let ball = b.id;
+                candidates.clear();
+                self.broad_phase.query(Rect::new(b.position.x, b.position.y, 0.0, 0.0), &mut candidates);
+                let mut inside = Vec::new();
+                for &index in &candidates {
+                    let body_id = self.colliders.id_at(index);
+                    let (bodies, layers) = self.colliders.bodies_and_layers_mut();
+                    if let StaticBody::LayerGate { shape, from, to, direction, .. } = &bodies[index] && shape.contains(b.position) {
+                        let crossing = b.velocity.dot(*direction);
+                        let layer = if b.layer == *from && crossing > 0.0 {
+                            *to
+                        } else if b.layer == *to && crossing < 0.0 {
+                            *from
+                        } else {
+                            continue;
+                        };
+                        b.layer = layer;
+                        self.events.push(GameEvent::LayerChanged { ball, body_id, layer });
                     }
-                    '4' => {
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y, thickness, height / 2.0 + thickness / 2.0, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                    if layers[index] != b.layer {
+                        continue;
                     }
-                    '5' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y, thickness, height / 2.0, WHITE);
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y + height / 2.0, thickness, height / 2.0, WHITE);
+                    if let StaticBody::Sensor { shape, occupants, lit, .. } = &mut bodies[index] && shape.contains(b.position) {
+                        *occupants += 1;
+                        if !b.sensors.contains(&body_id) {
+                            *lit = SENSOR_LIT_TIME;
+                            self.events.push(GameEvent::SensorEntered { ball, body_id });
+                        }
+                        inside.push(body_id);
                     }
-                    '6' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y, thickness, height, WHITE);
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y + height / 2.0, thickness, height / 2.0, WHITE);
+                }
+                for &body_id in b.sensors.iter().filter(|id| !inside.contains(id)) {
+                    self.events.push(GameEvent::SensorExited { ball, body_id });
+                }
+                b.sensors = inside;
+            }
+        }
+
+        //Catches balls rolling into saucers and locks, then lets them eject what they are done holding
+        fn update_catchers(&mut self, dt: f32) {
+            let mut candidates = Vec::new();
+            for ball in 0..self.balls.len() {
+                let b = &self.balls[ball];
+                if b.captured.is_some() {
+                    continue;
+                }
+                self.broad_phase.query(Rect::new(b.position.x, b.position.y, 0.0, 0.0), &mut candidates);
+                for &index in &candidates {
+                    let body = &self.colliders.bodies()[index];
+                    let b = &self.balls[ball];
+                    if self.colliders.layers()[index] != b.layer {
+                        continue;
                     }
-                    '7' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                    let Some(hold) = body.catch_point(b) else {
+                        continue;
+                    };
+                    //A lock only keeps the ball if another one can be served in its place, or if this fills it
+                    if let StaticBody::BallLock { capacity, locked, .. } = body
+                        && locked + 1 < *capacity
+                        && self.balls.len() + self.pending_balls >= MAX_BALLS {
+                        continue;
                     }
-                    '8' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y, thickness, height, WHITE);
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                    let serve = matches!(body, StaticBody::BallLock { capacity, locked, .. } if locked + 1 < *capacity);
+
+                    self.colliders.bodies_mut()[index].catch_ball();
+                    let body_id = self.colliders.id_at(index);
+                    let b = &mut self.balls[ball];
+                    b.position = hold;
+                    b.velocity = Vec2::ZERO;
+                    b.angular_velocity = 0.0;
+                    b.captured = Some(Capture::Body(body_id));
+                    self.events.push(GameEvent::BallCaptured { ball: b.id, body_id });
+                    if serve {
+                        self.add_ball();
                     }
-                    '9' => {
-                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
-                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
-                        draw_rectangle(current_corner.x, current_corner.y, thickness, height / 2.0, WHITE);
+                    break;
+                }
+            }
+
+            for index in 0..self.colliders.len() {
+                let Some(eject) = self.colliders.bodies_mut()[index].update_catcher(dt) else {
+                    continue;
+                };
+                let body_id = self.colliders.id_at(index);
+                let mut released = 0;
+                for b in self.balls.iter_mut().filter(|b| b.captured == Some(Capture::Body(body_id))) {
+                    b.captured = None;
+                    b.velocity = eject;
+                    released += 1;
+                    self.events.push(GameEvent::BallEjected { ball: b.id, body_id });
+                }
+                if matches!(self.colliders.bodies()[index], StaticBody::BallLock { .. }) {
+                    self.events.push(GameEvent::MultiballStarted { body_id, balls: released });
+                }
+            }
+        }
+
+        //Turns this step's hits into target state and completes banks
+        fn update_targets(&mut self, dt: f32, first_event: usize) {
+            for i in first_event..self.events.len() {
+                if let GameEvent::BallHit { body_id, impulse, .. } = self.events[i]
+                    && impulse > TARGET_MIN_IMPULSE
+                    && self.colliders.get_mut(body_id).is_some_and(|body| body.hit_target()) {
+                    self.events.push(GameEvent::TargetHit { body_id });
+                }
+            }
+
+            for (index, bank) in self.banks.iter_mut().enumerate() {
+                if bank.is_resetting() {
+                    bank.update(dt, &mut self.colliders);
+                } else if bank.is_complete(&self.colliders) {
+                    self.events.push(GameEvent::TargetBankCompleted { bank: index, bonus: bank.bonus });
+                    bank.schedule_reset(TARGET_BANK_RESET_TIME);
+                }
+            }
+        }
+
+        //Turns a magnet or force zone on or off, for `duration` seconds or for good if it is 0
+        pub fn switch_field(&mut self, id: ColliderId, enabled: bool, duration: f32) {
+            let Some(field) = self.colliders.get_mut(id).and_then(StaticBody::field_mut) else {
+                return;
+            };
+            if field.switch(enabled, duration) {
+                self.events.push(GameEvent::FieldSwitched { body_id: id, enabled });
+            }
+        }
+
+        //Events since the last call, for listeners outside the simulation (sound, effects)
+        pub fn take_events(&mut self) -> Vec<GameEvent> {
+            std::mem::take(&mut self.events)
+        }
+
+        //Launch button pulls the plunger back at a steady rate, the analog pull sets it directly.
+        //Let go, the spring drives it forward and it pushes whatever ball it touches.
+        fn update_plunger(&mut self, dt: f32, input: &InputState) {
+            let Some(StaticBody::Plunger { travel, spring, pull, speed, .. }) = self.colliders.get_mut(self.launcher) else {
+                return;
+            };
+            let target = if self.auto_plunge {
+                Some(*pull + *travel / AUTO_PLUNGER_PULL_TIME * dt)
+            } else if input.plunger > 0.0 {
+                Some(input.plunger.min(1.0) * *travel)
+            } else if input.launch {
+                Some(*pull + *travel / PLUNGER_PULL_TIME * dt)
+            } else {
+                None
+            };
+
+            let mut stroke_done = false;
+            match target {
+                Some(target) => {
+                    let target = target.min(*travel);
+                    *speed = (*pull - target) / dt;
+                    *pull = target;
+                    if self.auto_plunge && *pull >= *travel {
+                        self.auto_plunge = false;
                     }
-                    ' ' => {
-                        current_corner += vec2((width + thickness + 3.0) / 1.5, 0.0);
+                },
+                None if *pull > 0.0 => {
+                    *speed += *spring * *pull * dt;
+                    *pull = (*pull - *speed * dt).max(0.0);
+                },
+                //Back at rest, the last step of the stroke has pushed the ball already
+                None => {
+                    stroke_done = *speed > 0.0;
+                    *speed = 0.0;
+                },
+            }
+            let pulled = *pull > 0.0;
+
+            //Served balls drop onto the plunger once it moves
+            if pulled {
+                for b in self.balls.iter_mut().filter(|b| b.captured == Some(Capture::Launcher)) {
+                    b.captured = None;
+                }
+            }
+            if target.is_some() && pulled {
+                self.launch_contacts.clear();
+            }
+            if stroke_done && !self.launch_contacts.is_empty() {
+                //Balls that drained on the way are not reported
+                for ball in std::mem::take(&mut self.launch_contacts) {
+                    if let Some(b) = self.balls.iter().find(|b| b.id == ball) {
+                        self.events.push(GameEvent::BallLaunched { ball, speed: b.velocity.length() });
                     }
-                    _ => {}
                 }
-        
-                current_corner -= vec2(width + thickness + 3.0, 0.0); // spacing between digits
+                if self.ball_save_armed {
+                    self.ball_save_timer = self.ball_save;
+                    self.ball_save_armed = false;
+                }
             }
         }
-        
+
+        //Held by a ball lock, waiting for multiball
+        fn is_locked(&self, ball: &PhysicsBody) -> bool {
+            matches!(ball.captured, Some(Capture::Body(id)) if matches!(self.colliders.get(id), Some(StaticBody::BallLock { .. })))
+        }
+
+        //Removes every ball and puts a new one in the launcher
         pub fn restart_ball(&mut self) {
-            self.ball = PhysicsBody::new(
-                vec2(465.0, 600.0),
-                vec2(0.0, 0.0),
-                self.ball.radius
-            );
+            self.balls.clear();
+            self.pending_balls = 0;
+            for body in self.colliders.bodies_mut() {
+                body.reset_catcher();
+            }
+            self.launch_contacts.clear();
+            self.auto_plunge = false;
+            self.ball_save_armed = true;
+            self.ball_save_timer = 0.0;
+            let ball = self.launcher_ball();
+            self.balls.push(ball);
+        }
+
+        //New ball held at the spawn point until the launcher fires
+        fn launcher_ball(&mut self) -> PhysicsBody {
+            let id = BallId(self.next_ball_id);
+            self.next_ball_id += 1;
+            PhysicsBody { id, captured: Some(Capture::Launcher), ..PhysicsBody::new(self.ball_spawn, Vec2::ZERO, self.ball_radius) }
         }
 
-        pub fn update_flipper(&mut self, dt: f32, index: usize, key: KeyCode) {
-            let mut left_flipper: &mut StaticBody = self.colliders.get_mut(index).expect("No left flipper");
+        //Queues another ball, it appears at the spawn point once that is free
+        pub fn add_ball(&mut self) {
+            if self.balls.len() + self.pending_balls < MAX_BALLS {
+                self.pending_balls += 1;
+            }
+        }
+
+        fn spawn_pending_ball(&mut self) {
+            if self.pending_balls == 0 {
+                return;
+            }
+            let blocked = self.balls.iter()
+                .any(|b| b.position.distance(self.ball_spawn) < b.radius + self.ball_radius);
+            if !blocked {
+                self.pending_balls -= 1;
+                let ball = self.launcher_ball();
+                self.balls.push(ball);
+            }
+        }
+
+        //Coil or return spring accelerate the flipper, end stops bounce it back.
+        //Ball hits have already changed angular_velocity during the last step.
+        pub fn update_flipper(&mut self, dt: f32, id: ColliderId, held: bool) {
+            let mut left_flipper: &mut StaticBody = self.colliders.get_mut(id).expect("No flipper with this id");
             if let StaticBody::Flipper {
                 current_rotation, 
                 rotation_max, 
                 rotation_min, 
                 angular_velocity,
+                drive,
                 .. 
             } = &mut left_flipper {
-                let up = (*rotation_min - *rotation_max).signum();
-                if is_key_down(key) { *angular_velocity = -FLIPPER_SPEED * up; }
-                else                { *angular_velocity =  FLIPPER_SPEED * up; }
-
-                let previous_rotation = *current_rotation;
-                *current_rotation = (*current_rotation + *angular_velocity * dt)
-                    .clamp(
-                        f32::min(*rotation_min, *rotation_max), 
-                        f32::max(*rotation_min, *rotation_max)
-                    );
-                if previous_rotation == *current_rotation {
-                    *angular_velocity = 0.0;
+                let up = (*rotation_max - *rotation_min).signum();
+                let torque = if held { drive.coil_torque * up } else { -drive.return_torque * up };
+                *angular_velocity += torque / drive.inertia * dt;
+
+                let rotation = *current_rotation + *angular_velocity * dt;
+                let limit_low = f32::min(*rotation_min, *rotation_max);
+                let limit_high = f32::max(*rotation_min, *rotation_max);
+                *current_rotation = rotation.clamp(limit_low, limit_high);
+                if rotation != *current_rotation {
+                    //Hit an end stop
+                    *angular_velocity *= -drive.end_restitution;
+                    if angular_velocity.abs() < FLIPPER_STOP_SPEED {
+                        *angular_velocity = 0.0;
+                    }
                 }
             }
         }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use macroquad::prelude::*;
+
+        use super::{GameWorld, DEFAULT_TABLE, GRAVITY, PHYSICS_TARGET_FRAMETIME, TARGET_BANK_RESET_TIME, TARGET_SCORE};
+        use crate::{events::events::GameEvent, input::input::InputState, physics_obj::physics_obj::{BallId, Capture, PhysicsBody}, static_obj::static_obj::StaticBody, table::table::TableDescription};
+
+        const DT: f32 = 1.0 / 144.0;
+
+        //Table with only a plunger in the corner, plus `bodies`
+        fn world(bodies: &str) -> GameWorld {
+            world_with_rules(bodies, "")
+        }
+
+        //Same with more table fields, such as banks
+        fn world_with_rules(bodies: &str, rules: &str) -> GameWorld {
+            let source = format!(r#"TableDescription(
+                ball: BallSpawn(position: (465.0, 600.0), radius: 10.0),
+                launcher: "plunger",
+                drain_y: 1000.0,
+                bodies: [
+                    Plunger(position: (465.0, 615.0), size: (28.0, 10.0), travel: 30.0, spring: 750.0, color: "GRAY", name: "plunger"),
+                    {bodies}
+                ],
+                {rules}
+            )"#);
+            GameWorld::new(TableDescription::from_ron(&source).and_then(|d| d.build()).expect("test table loads"))
+        }
+
+        //Only ball falling at `speed` onto the top surface at `point`, returns the events of the step it lands
+        fn drop_onto(world: &mut GameWorld, point: Vec2, speed: f32) -> Vec<GameEvent> {
+            world.balls = vec![ball(point - vec2(0.0, 10.5), vec2(0.0, speed))];
+            step(world)
+        }
+
+        fn ball(position: Vec2, velocity: Vec2) -> PhysicsBody {
+            PhysicsBody::new(position, velocity, 10.0)
+        }
+
+        //One step with `input`, returns its events
+        fn step_with(world: &mut GameWorld, input: &InputState) -> Vec<GameEvent> {
+            world.physics_update(DT, input);
+            world.take_events()
+        }
+
+        fn step(world: &mut GameWorld) -> Vec<GameEvent> {
+            step_with(world, &InputState::default())
+        }
+
+        #[test]
+        fn life_is_lost_only_when_the_last_ball_drains() {
+            let mut world = world("");
+            world.balls = vec![ball(vec2(200.0, 1010.0), vec2(0.0, 300.0)), ball(vec2(200.0, 300.0), Vec2::ZERO)];
+
+            let events = step(&mut world);
+            assert_eq!(events.iter().filter(|e| matches!(e, GameEvent::BallDrained { .. })).count(), 1);
+            assert_eq!(world.lives(), 3);
+            assert_eq!(world.balls().len(), 1);
+
+            world.balls[0].position.y = 1010.0;
+            let events = step(&mut world);
+            assert_eq!(events.iter().filter(|e| matches!(e, GameEvent::BallDrained { .. })).count(), 1);
+            assert_eq!(world.lives(), 2);
+            //Next ball waits on the plunger
+            assert_eq!(world.balls().len(), 1);
+            assert_eq!(world.balls()[0].captured, Some(Capture::Launcher));
+        }
+
+        #[test]
+        fn extra_ball_is_awarded_once_per_light() {
+            let mut world = world(r#"
+                Sensor(shape: Circle(position: (100.0, 100.0), radius: 20.0), color: "GRAY", tags: ["light_extra_ball"]),
+                Circle(position: (300.0, 300.0), radius: 20.0, color: "GRAY", tags: ["extra_ball"]),
+            "#);
+            let hit_award = |world: &mut GameWorld| {
+                let events = drop_onto(world, vec2(300.0, 280.0), 300.0);
+                assert!(events.iter().any(|e| matches!(e, GameEvent::BallHit { .. })), "ball missed the award body");
+                events.contains(&GameEvent::ExtraBallAwarded)
+            };
+
+            assert!(!hit_award(&mut world), "awarded without being lit");
+            assert_eq!(world.balls().len(), 1);
+
+            world.balls = vec![ball(vec2(100.0, 100.0), Vec2::ZERO)];
+            assert!(step(&mut world).contains(&GameEvent::ExtraBallLit));
+            assert!(world.extra_ball_lit());
+
+            assert!(hit_award(&mut world));
+            assert!(!world.extra_ball_lit());
+            assert_eq!(world.balls().iter().filter(|b| b.captured == Some(Capture::Launcher)).count(), 1, "extra ball was not served");
+
+            assert!(!hit_award(&mut world), "awarded twice for one light");
+        }
 
-        pub fn initialize_world(&mut self) {
-            // Flippers
-            flipper!(self, vec2(180.0, 630.0), vec2( 24.0, 0.0), vec2(60.0, 10.0),  0.16 * PI, -0.5, PURPLE);
-            flipper!(self, vec2(320.0, 630.0), vec2(-24.0, 0.0), vec2(60.0, 10.0), -0.16 * PI,  0.5, PURPLE);
-
-            // Floor
-            rect!(self, vec2(116.0, 595.1),  vec2(144.8, 10.0),  0.16 * PI, GRAY);
-            rect!(self, vec2(384.0, 595.1), vec2(144.8, 10.0), -0.16 * PI, GRAY);
-
-            // Lower floor
-            rect!(self, vec2(100.0, 627.0), vec2(109.0, 10.0), 0.16 * PI, GRAY);
-            rect!(self, vec2(400.0, 627.0), vec2(109.0, 10.0), -0.16 * PI, GRAY);
-
-            // Walls
-            rect!(self, vec2(490.0, 350.0), vec2(20.0, 700.0), 0.0, GRAY);
-            rect!(self, vec2(10.0, 350.0), vec2(20.0, 700.0), 0.0, GRAY);
-
-            // Roof
-            rect!(self, vec2(250.0, 10.0), vec2(500.0, 20.0), 0.0, GRAY);
-
-            // Inside wall
-            rect!(self, vec2(445.0, 435.0), vec2(10.0, 390.0), 0.0, GRAY);
-            rect!(self, vec2(465.0, 620.0), vec2(30.0, 20.0), 0.0, GRAY);
-
-            //Opposite inside wall
-            rect!(self, vec2(55.0, 530.0), vec2(10.0, 70.0), 0.0, GRAY);
-            rect!(self, vec2(55.0, 630.0), vec2(10.0, 50.0), 0.0, GRAY);
-            rect!(self, vec2(35.0, 650.0), vec2(30.0, 10.0), 0.0, LIGHTGRAY, 200.0);
-            rect!(self, vec2(50.0, 450.0), vec2(100.0, 10.0), PI * -0.25, GRAY);
-
-            // Enter curves
-            curve!(self, vec2(250.0, 250.0), 230.0, 20.0, -2.11, 0.0, 30, GRAY);
-            curve!(self, vec2(250.0, 250.0), 200.0, -10.0, -1.15, 0.0, 0, GRAY);
-            curve!(self, vec2(75.0, 75.0), 50.0, 50.0, PI * 0.665, PI * -0.13, 30, GRAY);
-
-            // Bumper
-            circle!(self, vec2(75.0, 75.0), 15.0, WHITE, 100.0);
-
-            // Outside continue
-            curve!(self, vec2(250.0, 250.0), 230.0, 110.0, PI * 0.75, -2.55, 30, GRAY);
-
-            // Tunnel
-            curve!(self, vec2(250.0, 250.0), 190.0, 10.0, -1.15, 0.5, 30, GRAY);
-            curve!(self, vec2(250.0, 250.0), 165.0, -10.0, -1.15, 0.3, 30, GRAY);
-            curve!(self, vec2(250.0, 250.0), 155.0, 10.0, -1.15, 0.3, 0, GREEN);
-
-            // Top 2 splitters
-            rect!(self, vec2(230.0, 130.0), vec2(10.0, 30.0), 0.0, GRAY);
-            rect!(self, vec2(270.0, 130.0), vec2(10.0, 30.0), 0.0, GRAY);
-
-            //Middle angled
-            rect!(self, vec2(245.0, 310.0), vec2(40.0, 10.0), PI * 0.16, YELLOW, 50.0);
-            rect!(self, vec2(250.0, 300.0), vec2(50.0, 20.0), PI * 0.16, GRAY);
-
-            //Middle bumpers
-            circle!(self, vec2(260.0, 280.0), 15.0, WHITE, 100.0);
-            circle!(self, vec2(310.0, 220.0), 15.0, WHITE, 100.0);
-            circle!(self, vec2(210.0, 230.0), 15.0, WHITE, 100.0);
-
-            //Left top abomination
-            curve!(self, vec2(250.0, 250.0), 200.0, -5.0, -2.24, -1.96, 20, GRAY);
-            rect!(self, vec2(130.0, 110.0), vec2(10.0, 30.0), 0.0, GRAY);
-            rect!(self, vec2(170.0, 85.0), vec2(10.0, 30.0), 0.0, GRAY);
-            rect!(self, vec2(150.0, 112.0), vec2(54.0, 10.0), PI * -0.175, YELLOW, 50.0);
-            rect!(self, vec2(150.0, 94.0), vec2(45.0, 21.0), PI * -0.175, GRAY);
-
-            //Bottom left bumper
-            rect!(self, vec2(130.0, 535.0), vec2(25.0, 40.0), 0.0, DARKBLUE, 0.0);
-            rect!(self, vec2(124.0, 505.0), vec2(13.0, 25.0), 0.0, DARKBLUE, 0.0);
-            rect!(self, vec2(136.0, 545.0), vec2(37.0, 20.0), 0.0, DARKBLUE, 0.0);
-            rect!(self, vec2(140.0, 520.0), vec2(8.0, 70.0), PI * -0.16, WHITE, 150.0);
-            rect!(self, vec2(139.0, 522.0), vec2(5.0, 75.0), PI * -0.16, DARKBLUE, 0.0);
-
-            //Bottom right bumper
-            rect!(self, vec2(370.0, 535.0), vec2(25.0, 40.0), 0.0, DARKBLUE, 0.0);
-            rect!(self, vec2(376.0, 505.0), vec2(13.0, 25.0), 0.0, DARKBLUE, 0.0);
-            rect!(self, vec2(364.0, 545.0), vec2(37.0, 20.0), 0.0, DARKBLUE, 0.0);
-            rect!(self, vec2(360.0, 520.0), vec2(8.0, 70.0), PI * 0.16, WHITE, 150.0);
-            rect!(self, vec2(361.0, 522.0), vec2(5.0, 75.0), PI * 0.16, DARKBLUE, 0.0);
-            
-            //Spinner in tunnel
-            self.colliders.push(StaticBody::Spinner { 
-                position: vec2(425.0, 280.0), dimensions: vec2(20.0, 20.0), rotation: 0.2, acc_velocity: 0.0, top_down_rotation: 0.0, color: LIGHTGRAY 
+        #[test]
+        fn events_of_one_step_name_balls_by_id() {
+            let mut world = world(r#"Circle(position: (300.0, 300.0), radius: 20.0, color: "GRAY", name: "post"),"#);
+            let post = world.colliders().find("post").unwrap();
+            world.balls = vec![
+                PhysicsBody { id: BallId(10), ..ball(vec2(200.0, 1010.0), vec2(0.0, 300.0)) },
+                PhysicsBody { id: BallId(11), ..ball(vec2(300.0, 269.0), vec2(0.0, 300.0)) },
+            ];
+
+            let events = step(&mut world);
+            assert!(events.contains(&GameEvent::BallDrained { ball: BallId(10) }), "{events:?}");
+            let hits: Vec<_> = events.iter().filter_map(|e| match e {
+                GameEvent::BallHit { ball, body_id, .. } => Some((*ball, *body_id)),
+                _ => None,
+            }).collect();
+            assert_eq!(hits, [(BallId(11), post)]);
+            assert_eq!(events.len(), 2, "{events:?}");
+            assert!(step(&mut world).is_empty());
+        }
+
+        #[test]
+        fn flipper_activation_is_reported_once_per_press() {
+            let mut world = world(r#"Flipper(origin: (180.0, 630.0), offset: (24.0, 0.0), size: (60.0, 10.0), rotation_min: 0.5, rotation_max: -0.5, color: "GRAY", action: "left", name: "flipper"),"#);
+            let flipper = world.colliders().find("flipper").unwrap();
+            let held = InputState { flippers: vec![true], ..Default::default() };
+            let released = InputState { flippers: vec![false], ..Default::default() };
+
+            let presses = [&held, &held, &held, &released, &held].map(|input| {
+                step_with(&mut world, input).iter().filter(|e| **e == GameEvent::FlipperActivated { body_id: flipper }).count()
             });
+            assert_eq!(presses, [1, 0, 0, 0, 1]);
+        }
+
+        #[test]
+        fn bodies_moved_by_name_collide_where_they_are_now() {
+            let mut world = world(r#"Circle(position: (100.0, 300.0), radius: 20.0, color: "GRAY", name: "post"),"#);
+            let post = world.colliders().find("post").unwrap();
+            if let Some(StaticBody::Circle { position, .. }) = world.colliders_mut().find_mut("post") {
+                *position = vec2(300.0, 300.0);
+            }
+            world.balls = vec![ball(vec2(300.0, 269.0), vec2(0.0, 300.0))];
+            assert!(step(&mut world).iter().any(|e| matches!(e, GameEvent::BallHit { body_id, .. } if *body_id == post)));
         }
-    }
 
+        #[test]
+        fn flippers_follow_their_own_action() {
+            let mut world = world(r#"
+                Flipper(origin: (180.0, 630.0), offset: (24.0, 0.0), size: (60.0, 10.0), rotation_min: 0.5, rotation_max: -0.5, color: "GRAY", action: "left", name: "left"),
+                Flipper(origin: (100.0, 300.0), offset: (24.0, 0.0), size: (60.0, 10.0), rotation_min: 0.5, rotation_max: -0.5, color: "GRAY", action: "upper_left", name: "upper_left"),
+                Flipper(origin: (100.0, 500.0), offset: (24.0, 0.0), size: (60.0, 10.0), rotation_min: 0.5, rotation_max: -0.5, color: "GRAY", action: "left", name: "left_2"),
+            "#);
+            assert_eq!(world.flipper_actions(), ["left", "upper_left"]);
 
+            let input = InputState { flippers: vec![false, true], ..Default::default() };
+            for _ in 0..30 {
+                step_with(&mut world, &input);
+            }
+            let rotation = |name| match world.colliders().get(world.colliders().find(name).unwrap()) {
+                Some(StaticBody::Flipper { current_rotation, .. }) => *current_rotation,
+                _ => unreachable!(),
+            };
+            //Up against its end stop, give or take the bounce
+            assert!(rotation("upper_left") < -0.4, "upper flipper at {}", rotation("upper_left"));
+            assert_eq!(rotation("left"), 0.5);
+            assert_eq!(rotation("left_2"), 0.5);
+        }
 
-}
+        #[test]
+        fn flipper_accelerates_bounces_off_its_stop_and_falls_back() {
+            let mut world = world(r#"Flipper(origin: (180.0, 630.0), offset: (24.0, 0.0), size: (60.0, 10.0), rotation_min: 0.5, rotation_max: -0.5, color: "GRAY", action: "left", name: "flipper"),"#);
+            let flipper = world.colliders().find("flipper").unwrap();
+            let state = |world: &GameWorld| match world.colliders().get(flipper) {
+                Some(StaticBody::Flipper { current_rotation, angular_velocity, drive, .. }) => (*current_rotation, *angular_velocity, *drive),
+                _ => unreachable!(),
+            };
+
+            //Coil torque builds speed up step by step
+            world.update_flipper(DT, flipper, true);
+            let (_, first, drive) = state(&world);
+            assert!((first + drive.coil_torque / drive.inertia * DT).abs() < 1e-3, "first step at {first} rad/s");
+            world.update_flipper(DT, flipper, true);
+            assert!(state(&world).1 < first);
+
+            //Reaching the stop sends it back with end_restitution of its speed
+            let mut bounced = false;
+            for _ in 0..100 {
+                let (_, before, _) = state(&world);
+                world.update_flipper(DT, flipper, true);
+                let (rotation, after, _) = state(&world);
+                if rotation == -0.5 && after > 0.0 {
+                    let expected = -(before - drive.coil_torque / drive.inertia * DT) * drive.end_restitution;
+                    assert!((after - expected).abs() < 1e-2, "bounced back at {after}, expected {expected}");
+                    bounced = true;
+                    break;
+                }
+            }
+            assert!(bounced, "flipper never reached its stop");
+
+            for _ in 0..300 {
+                world.update_flipper(DT, flipper, false);
+            }
+            assert_eq!(state(&world).0, 0.5);
+            assert_eq!(state(&world).1, 0.0);
+        }
+
+        #[test]
+        fn bank_completes_once_and_comes_back_up() {
+            let mut world = world_with_rules(r#"
+                DropTarget(position: (100.0, 300.0), size: (40.0, 6.0), color: "ORANGE", name: "drop"),
+                StandupTarget(position: (300.0, 300.0), size: (40.0, 6.0), color: "LIME", name: "standup"),
+            "#, r#"banks: [(targets: ["drop", "standup"], bonus: 1000.0)],"#);
+            let drop = world.colliders().find("drop").unwrap();
+            let standup = world.colliders().find("standup").unwrap();
+            let is_hit = |world: &GameWorld, id| world.colliders().get(id).unwrap().is_target_hit();
+
+            //Resting on it doesn't count
+            world.balls = vec![ball(vec2(300.0, 287.0), vec2(0.0, 5.0))];
+            let events: Vec<GameEvent> = (0..10).flat_map(|_| step(&mut world)).collect();
+            assert!(events.iter().any(|e| matches!(e, GameEvent::BallHit { body_id, .. } if *body_id == standup)));
+            assert!(!events.contains(&GameEvent::TargetHit { body_id: standup }));
+
+            assert!(drop_onto(&mut world, vec2(100.0, 297.0), 300.0).contains(&GameEvent::TargetHit { body_id: drop }));
+            assert!(is_hit(&world, drop));
+            //Dropped target lets the ball through
+            assert!(drop_onto(&mut world, vec2(100.0, 297.0), 300.0).iter().all(|e| !matches!(e, GameEvent::BallHit { .. })));
 
+            let score = world.score();
+            let events = drop_onto(&mut world, vec2(300.0, 297.0), 300.0);
+            assert!(events.contains(&GameEvent::TargetHit { body_id: standup }));
+            assert!(events.contains(&GameEvent::TargetBankCompleted { bank: 0, bonus: 1000.0 }));
+            assert!(world.score() - score >= 1000.0 + TARGET_SCORE, "scored {}", world.score() - score);
+
+            //Stays down until the reset delay runs out, without completing again
+            world.balls.clear();
+            let mut completed = 0;
+            for _ in 0..(TARGET_BANK_RESET_TIME / DT) as usize - 2 {
+                completed += step(&mut world).iter().filter(|e| matches!(e, GameEvent::TargetBankCompleted { .. })).count();
+            }
+            assert_eq!(completed, 0);
+            assert!(is_hit(&world, drop) && is_hit(&world, standup));
+            for _ in 0..4 {
+                step(&mut world);
+            }
+            assert!(!is_hit(&world, drop) && !is_hit(&world, standup));
+        }
+
+        #[test]
+        fn pop_bumper_kicks_hard_hits_after_its_cooldown() {
+            let mut world = world(r#"PopBumper(position: (300.0, 300.0), radius: 20.0, color: "WHITE", min_speed: 150.0, impulse: 250.0, cooldown: 0.5, name: "bumper"),"#);
+            let bumper = world.colliders().find("bumper").unwrap();
+            let kicked = |events: &[GameEvent]| events.contains(&GameEvent::KickerFired { body_id: bumper });
+
+            let events = drop_onto(&mut world, vec2(300.0, 280.0), 100.0);
+            assert!(events.iter().any(|e| matches!(e, GameEvent::BallHit { .. })) && !kicked(&events), "{events:?}");
+
+            assert!(kicked(&drop_onto(&mut world, vec2(300.0, 280.0), 300.0)));
+            //Bounce plus the kick on top
+            assert!(world.balls()[0].velocity.y < -400.0, "left at {}", world.balls()[0].velocity);
+            assert!(!kicked(&drop_onto(&mut world, vec2(300.0, 280.0), 300.0)), "fired during the cooldown");
+
+            world.balls.clear();
+            for _ in 0..(0.5 / DT) as usize {
+                step(&mut world);
+            }
+            assert!(kicked(&drop_onto(&mut world, vec2(300.0, 280.0), 300.0)));
+        }
+
+        #[test]
+        fn sensor_reports_entering_and_leaving_once() {
+            let mut world = world(r#"Sensor(shape: Rectangle(position: (300.0, 300.0), size: (40.0, 200.0)), color: "GRAY", name: "lane"),"#);
+            let lane = world.colliders().find("lane").unwrap();
+            world.balls = vec![PhysicsBody { id: BallId(3), ..ball(vec2(200.0, 300.0), vec2(300.0, 0.0)) }];
+
+            let mut changes = Vec::new();
+            for _ in 0..100 {
+                changes.extend(step(&mut world).into_iter().filter(|e| matches!(e, GameEvent::SensorEntered { .. } | GameEvent::SensorExited { .. })));
+            }
+            assert_eq!(changes, [
+                GameEvent::SensorEntered { ball: BallId(3), body_id: lane },
+                GameEvent::SensorExited { ball: BallId(3), body_id: lane },
+            ]);
+            //Never touched the ball
+            assert_eq!(world.balls()[0].velocity.x, 300.0);
+        }
+
+        #[test]
+        fn layer_gate_moves_balls_crossing_it_either_way() {
+            let mut world = world(r#"LayerGate(shape: Rectangle(position: (300.0, 300.0), size: (20.0, 200.0)), from: 0, to: 1, direction: (1.0, 0.0), color: "GRAY", name: "gate"),"#);
+            let gate = world.colliders().find("gate").unwrap();
+            let cross = |world: &mut GameWorld, velocity: Vec2| {
+                let layer = world.balls.first().map_or(0, |b| b.layer);
+                world.balls = vec![PhysicsBody { layer, ..ball(vec2(300.0 - velocity.x.signum() * 20.0, 300.0), velocity) }];
+                (0..20).flat_map(|_| step(world)).filter(|e| matches!(e, GameEvent::LayerChanged { .. })).collect::<Vec<_>>()
+            };
+
+            assert_eq!(cross(&mut world, vec2(300.0, 0.0)), [GameEvent::LayerChanged { ball: BallId(0), body_id: gate, layer: 1 }]);
+            //Already up, crossing the same way again changes nothing
+            assert_eq!(cross(&mut world, vec2(300.0, 0.0)), []);
+            assert_eq!(world.balls()[0].layer, 1);
+            assert_eq!(cross(&mut world, vec2(-300.0, 0.0)), [GameEvent::LayerChanged { ball: BallId(0), body_id: gate, layer: 0 }]);
+            assert_eq!(world.balls()[0].layer, 0);
+        }
+
+        #[test]
+        fn balls_only_touch_bodies_on_their_layer() {
+            let mut world = world(r#"Rectangle(position: (400.0, 300.0), size: (4.0, 400.0), color: "GRAY", layer: 1),"#);
+            for layer in [0, 1] {
+                world.balls = vec![PhysicsBody { layer, ..ball(vec2(380.0, 300.0), vec2(600.0, 0.0)) }];
+                for _ in 0..10 {
+                    step(&mut world);
+                }
+                let x = world.balls()[0].position.x;
+                assert_eq!(x > 400.0, layer == 0, "ball on layer {layer} ended up at x = {x}");
+            }
+        }
+
+        #[test]
+        fn saucer_holds_slow_balls_then_ejects_them() {
+            let mut world = world(r#"Saucer(position: (200.0, 300.0), radius: 10.0, hold_time: 1.0, eject_angle: -1.5707964, eject_speed: 400.0, color: "ORANGE", name: "saucer"),"#);
+            let saucer = world.colliders().find("saucer").unwrap();
+
+            world.balls = vec![ball(vec2(200.0, 300.0), vec2(300.0, 0.0))];
+            assert!(!step(&mut world).iter().any(|e| matches!(e, GameEvent::BallCaptured { .. })), "caught a fast ball");
+
+            world.balls = vec![ball(vec2(203.0, 300.0), vec2(20.0, 0.0))];
+            assert!(step(&mut world).contains(&GameEvent::BallCaptured { ball: BallId(0), body_id: saucer }));
+            let mut held_steps = 0;
+            while !step(&mut world).contains(&GameEvent::BallEjected { ball: BallId(0), body_id: saucer }) {
+                assert_eq!(world.balls()[0].position, vec2(200.0, 300.0), "ball moved while held");
+                held_steps += 1;
+                assert!(held_steps < 200, "ball was never ejected");
+            }
+            assert!((held_steps as f32 * DT - 1.0).abs() < 2.0 * DT, "held for {held_steps} steps");
+            assert!(world.balls()[0].velocity.abs_diff_eq(vec2(0.0, -400.0), 1e-3), "ejected at {}", world.balls()[0].velocity);
+        }
+
+        #[test]
+        fn ball_lock_serves_a_new_ball_then_starts_multiball() {
+            let mut world = world(r#"BallLock(position: (300.0, 300.0), radius: 10.0, capacity: 2, eject_angle: -1.5707964, color: "MAGENTA", name: "lock"),"#);
+            let lock = world.colliders().find("lock").unwrap();
+
+            world.balls = vec![ball(vec2(300.0, 300.0), Vec2::ZERO)];
+            assert!(step(&mut world).contains(&GameEvent::BallCaptured { ball: BallId(0), body_id: lock }));
+            assert_eq!(world.balls().len(), 2);
+            let served = &mut world.balls[1];
+            assert_eq!(served.captured, Some(Capture::Launcher));
+
+            //Served ball rolls in too and fills the lock
+            served.captured = None;
+            served.position = vec2(300.0, 300.0);
+            let served = served.id;
+            let events: Vec<GameEvent> = (0..10).flat_map(|_| step(&mut world)).collect();
+            assert!(events.contains(&GameEvent::BallCaptured { ball: served, body_id: lock }), "{events:?}");
+            assert!(events.contains(&GameEvent::MultiballStarted { body_id: lock, balls: 2 }), "{events:?}");
+            assert_eq!(events.iter().filter(|e| matches!(e, GameEvent::BallEjected { .. })).count(), 2);
+            assert!(world.balls().iter().all(|b| b.captured.is_none()));
+        }
+
+        #[test]
+        fn launch_speed_grows_with_the_pull() {
+            let launch = |pull: f32| {
+                let mut world = world("");
+                let pulled = InputState { plunger: pull, ..Default::default() };
+                for _ in 0..72 {
+                    step_with(&mut world, &pulled);
+                }
+                for _ in 0..72 {
+                    if let Some(speed) = step(&mut world).iter().find_map(|e| match e {
+                        GameEvent::BallLaunched { speed, .. } => Some(*speed),
+                        _ => None,
+                    }) {
+                        return speed;
+                    }
+                }
+                panic!("ball pulled back to {pull} was never launched");
+            };
+
+            let speeds = [0.25, 0.5, 0.75, 1.0].map(launch);
+            assert!(speeds.windows(2).all(|w| w[0] < w[1]), "launch speeds {speeds:?}");
+        }
+
+        #[test]
+        fn ball_save_serves_and_auto_plunges_a_drained_ball() {
+            let mut world = world_with_rules("", "ball_save: 5.0,");
+            let pulled = InputState { plunger: 1.0, ..Default::default() };
+            for _ in 0..72 {
+                step_with(&mut world, &pulled);
+            }
+            assert!((0..72).flat_map(|_| step(&mut world)).any(|e| matches!(e, GameEvent::BallLaunched { .. })));
+
+            world.balls[0].position.y = 1010.0;
+            assert!(step(&mut world).contains(&GameEvent::BallSaved));
+            assert_eq!(world.lives(), 3);
+            let launched = (0..288).flat_map(|_| step(&mut world)).any(|e| matches!(e, GameEvent::BallLaunched { .. }));
+            assert!(launched, "saved ball was not plunged");
+
+            //Only once per ball
+            world.balls[0].position.y = 1010.0;
+            assert!(!step(&mut world).contains(&GameEvent::BallSaved));
+            assert_eq!(world.lives(), 2);
+        }
+
+        #[test]
+        fn force_zone_only_accelerates_balls_while_switched_on() {
+            let mut world = world_with_rules(r#"
+                ForceZone(shape: Rectangle(position: (200.0, 300.0), size: (200.0, 400.0)), angle: 0.0, strength: 1000.0, enabled: false, color: "GRAY", name: "wind"),
+                Sensor(shape: Circle(position: (400.0, 100.0), radius: 10.0), color: "GRAY", name: "switch"),
+            "#, r#"field_switches: [(trigger: "switch", fields: ["wind"], duration: 0.5)],"#);
+            let wind = world.colliders().find("wind").unwrap();
+            let velocity_after = |world: &mut GameWorld, steps| {
+                world.balls = vec![ball(vec2(200.0, 200.0), Vec2::ZERO)];
+                for _ in 0..steps {
+                    step(world);
+                }
+                world.balls()[0].velocity
+            };
+
+            //Gravity alone
+            let velocity = velocity_after(&mut world, 36);
+            assert_eq!(velocity.x, 0.0);
+            assert!((velocity.y - GRAVITY.y * 36.0 * DT).abs() < 1e-2, "fell at {velocity}");
+
+            //Rolling over the switch turns it on for half a second
+            world.balls = vec![ball(vec2(400.0, 100.0), Vec2::ZERO)];
+            assert!(step(&mut world).contains(&GameEvent::FieldSwitched { body_id: wind, enabled: true }));
+            let velocity = velocity_after(&mut world, 36);
+            assert!((velocity.x - 1000.0 * 36.0 * DT).abs() < 1e-1, "pushed at {velocity}");
+
+            let switched_off = (0..72).flat_map(|_| step(&mut world)).any(|e| e == GameEvent::FieldSwitched { body_id: wind, enabled: false });
+            assert!(switched_off, "switch did not time out");
+            assert_eq!(velocity_after(&mut world, 36).x, 0.0);
+        }
+
+        #[test]
+        fn moving_body_hands_its_velocity_to_the_ball() {
+            let mut world = world(r#"Kinematic(shape: Rectangle(position: (0.0, 0.0), size: (10.0, 100.0)), keyframes: [(time: 0.0, position: (100.0, 300.0)), (time: 1.0, position: (300.0, 300.0))], color: "GRAY", name: "paddle"),"#);
+            let paddle = world.colliders().find("paddle").unwrap();
+            world.balls = vec![ball(vec2(130.0, 300.0), Vec2::ZERO)];
+            let hit = (0..36).flat_map(|_| step(&mut world)).any(|e| matches!(e, GameEvent::BallHit { body_id, .. } if body_id == paddle));
+            assert!(hit, "paddle never reached the ball");
+            //Bounced off a surface moving at 200 px/s
+            assert!(world.balls()[0].velocity.x > 200.0, "ball left at {}", world.balls()[0].velocity);
+        }
+
+        #[test]
+        fn playing_a_recording_ends_in_the_recorded_state() {
+            let mut recorded = GameWorld::create();
+            recorded.start_recording(DEFAULT_TABLE);
+            for step in 0..(10.0 / DT) as usize {
+                //Pull and release the plunger, then flip whenever a ball comes down
+                let flip = recorded.balls().iter().any(|b| b.position.y > 560.0);
+                let input = InputState {
+                    flippers: vec![flip; recorded.flipper_actions().len()],
+                    launch: step < 288,
+                    ..Default::default()
+                };
+                step_with(&mut recorded, &input);
+            }
+            let replay = recorded.finish_recording().unwrap();
+            assert!(replay.result.as_ref().unwrap().score > 0.0, "nothing happened in the recording");
+
+            let mut played = GameWorld::create();
+            played.play_replay(&replay);
+            //Live input is ignored while playing
+            let live = InputState { flippers: vec![true; played.flipper_actions().len()], launch: true, ..Default::default() };
+            while played.is_replaying() {
+                played.physics(PHYSICS_TARGET_FRAMETIME, &live);
+            }
+            assert_eq!(Some(played.replay_result()), replay.result);
+        }
+    }
+}
@@ -1,7 +1,6 @@
 
-
 pub mod helper {
-    use macroquad::math::Vec2;
+    use macroquad::{color::{colors, Color}, input::KeyCode, math::Vec2};
     
     pub fn rotate_vec2(v: Vec2, angle: f32) -> Vec2 {
         let cos_theta = angle.cos();
@@ -12,6 +11,77 @@ pub mod helper {
         )
     }
     
+    //Fraction t in [0, 1] of `motion` at which a point starting outside the circle enters it
+    pub fn ray_circle_entry(origin: Vec2, motion: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+        let offset = origin - center;
+        let c = offset.length_squared() - radius * radius;
+        //Starting inside
+        if c < 0.0 { return None; }
+
+        let a = motion.length_squared();
+        let b = offset.dot(motion);
+        //Not moving or moving away
+        if a == 0.0 || b >= 0.0 { return None; }
+
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 { return None; }
+
+        let t = (-b - discriminant.sqrt()) / a;
+        if t <= 1.0 { Some(t.max(0.0)) } else { None }
+    }
+
+    //Fraction t in [0, 1] of `motion` at which a point starting inside the circle leaves it
+    pub fn ray_circle_exit(origin: Vec2, motion: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+        let offset = origin - center;
+        let c = offset.length_squared() - radius * radius;
+        let a = motion.length_squared();
+        if c > 0.0 || a == 0.0 { return None; }
+
+        let b = offset.dot(motion);
+        let t = (-b + (b * b - a * c).sqrt()) / a;
+        if t <= 1.0 { Some(t.max(0.0)) } else { None }
+    }
+
+    //Point on the segment a-b closest to p
+    pub fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+        let edge = b - a;
+        let length_squared = edge.length_squared();
+        if length_squared == 0.0 { return a; }
+        let t = ((p - a).dot(edge) / length_squared).clamp(0.0, 1.0);
+        a + edge * t
+    }
+
+    //Appends points along the cubic Bezier p0..p3 (p0 itself excluded) until no part of the curve
+    //is further than `tolerance` from the resulting polyline
+    pub fn flatten_cubic_bezier(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, tolerance: f32, out: &mut Vec<Vec2>) {
+        flatten_cubic_bezier_depth(p0, p1, p2, p3, tolerance, 0, out);
+    }
+
+    fn flatten_cubic_bezier_depth(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, tolerance: f32, depth: u32, out: &mut Vec<Vec2>) {
+        //Curve stays inside the hull of its control points, so close control points mean a flat curve
+        let flat = closest_point_on_segment(p1, p0, p3).distance(p1) <= tolerance
+            && closest_point_on_segment(p2, p0, p3).distance(p2) <= tolerance;
+        if flat || depth >= 16 {
+            out.push(p3);
+            return;
+        }
+
+        //de Casteljau split at the middle
+        let p01 = (p0 + p1) * 0.5;
+        let p12 = (p1 + p2) * 0.5;
+        let p23 = (p2 + p3) * 0.5;
+        let p012 = (p01 + p12) * 0.5;
+        let p123 = (p12 + p23) * 0.5;
+        let middle = (p012 + p123) * 0.5;
+        flatten_cubic_bezier_depth(p0, p01, p012, middle, tolerance, depth + 1, out);
+        flatten_cubic_bezier_depth(middle, p123, p23, p3, tolerance, depth + 1, out);
+    }
+
+    //Bezier control points (p1, p2) of the uniform Catmull-Rom span between p1 and p2
+    pub fn catmull_rom_controls(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> (Vec2, Vec2) {
+        (p1 + (p2 - p0) / 6.0, p2 - (p3 - p1) / 6.0)
+    }
+
     pub fn format_number(number: i32) -> String {
         let number_str = format!("{:.0}", number); // Convert to string with no decimals
         let mut result = String::new();
@@ -26,38 +96,143 @@ pub mod helper {
     
         result.chars().rev().collect() // Reverse the result to correct the order
     }
-}
 
-#[macro_export]
-macro_rules! flipper {
-    ($self:ident, $pos:expr, $pivot:expr, $size:expr, $angle:expr, $speed:expr, $color:expr) => {
-        $self.colliders.push(StaticBody::new_flipper($pos, $pivot, $size, $angle, $speed, $color));
-    };
-}
+    //Value following a command line flag, e.g. "--record out.ron"
+    pub fn arg_value(args: &[String], flag: &str) -> Option<String> {
+        args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+    }
 
-#[macro_export]
-macro_rules! rect {
-    ($self:ident, $pos:expr, $size:expr, $angle:expr, $color:expr) => {
-        $self.colliders.push(StaticBody::new_rectangle($pos, $size, $angle, $color, 0.0));
-    };
-    ($self:ident, $pos:expr, $size:expr, $angle:expr, $color:expr, $impact:expr) => {
-        $self.colliders.push(StaticBody::new_rectangle($pos, $size, $angle, $color, $impact));
-    };
-}
+    //Looks up macroquad color constants by name, case insensitive
+    pub fn color_from_name(name: &str) -> Option<Color> {
+        let color = match name.to_ascii_uppercase().as_str() {
+            "LIGHTGRAY" => colors::LIGHTGRAY,
+            "GRAY" => colors::GRAY,
+            "DARKGRAY" => colors::DARKGRAY,
+            "YELLOW" => colors::YELLOW,
+            "GOLD" => colors::GOLD,
+            "ORANGE" => colors::ORANGE,
+            "PINK" => colors::PINK,
+            "RED" => colors::RED,
+            "MAROON" => colors::MAROON,
+            "GREEN" => colors::GREEN,
+            "LIME" => colors::LIME,
+            "DARKGREEN" => colors::DARKGREEN,
+            "SKYBLUE" => colors::SKYBLUE,
+            "BLUE" => colors::BLUE,
+            "DARKBLUE" => colors::DARKBLUE,
+            "PURPLE" => colors::PURPLE,
+            "VIOLET" => colors::VIOLET,
+            "DARKPURPLE" => colors::DARKPURPLE,
+            "BEIGE" => colors::BEIGE,
+            "BROWN" => colors::BROWN,
+            "DARKBROWN" => colors::DARKBROWN,
+            "WHITE" => colors::WHITE,
+            "BLACK" => colors::BLACK,
+            "MAGENTA" => colors::MAGENTA,
+            _ => return None,
+        };
+        Some(color)
+    }
 
-#[macro_export]
-macro_rules! curve {
-    ($self:ident, $center:expr, $radius:expr, $width:expr, $start:expr, $end:expr, $segments:expr, $color:expr) => {
-        $self.colliders.push(StaticBody::new_curve($center, $radius, $width, $start, $end, $segments, $color));
-    };
-}
+    //Looks up a key by the name of its KeyCode variant ("Left", "LeftShift", "A", "Key1"...)
+    pub fn key_from_name(name: &str) -> Option<KeyCode> {
+        let key = match name {
+            "Space" => KeyCode::Space,
+            "Enter" => KeyCode::Enter,
+            "Tab" => KeyCode::Tab,
+            "Backspace" => KeyCode::Backspace,
+            "Escape" => KeyCode::Escape,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "LeftShift" => KeyCode::LeftShift,
+            "RightShift" => KeyCode::RightShift,
+            "LeftControl" => KeyCode::LeftControl,
+            "RightControl" => KeyCode::RightControl,
+            "LeftAlt" => KeyCode::LeftAlt,
+            "RightAlt" => KeyCode::RightAlt,
+            "Slash" => KeyCode::Slash,
+            "Period" => KeyCode::Period,
+            "Comma" => KeyCode::Comma,
+            "Semicolon" => KeyCode::Semicolon,
+            "Key0" => KeyCode::Key0,
+            "Key1" => KeyCode::Key1,
+            "Key2" => KeyCode::Key2,
+            "Key3" => KeyCode::Key3,
+            "Key4" => KeyCode::Key4,
+            "Key5" => KeyCode::Key5,
+            "Key6" => KeyCode::Key6,
+            "Key7" => KeyCode::Key7,
+            "Key8" => KeyCode::Key8,
+            "Key9" => KeyCode::Key9,
+            "A" => KeyCode::A,
+            "B" => KeyCode::B,
+            "C" => KeyCode::C,
+            "D" => KeyCode::D,
+            "E" => KeyCode::E,
+            "F" => KeyCode::F,
+            "G" => KeyCode::G,
+            "H" => KeyCode::H,
+            "I" => KeyCode::I,
+            "J" => KeyCode::J,
+            "K" => KeyCode::K,
+            "L" => KeyCode::L,
+            "M" => KeyCode::M,
+            "N" => KeyCode::N,
+            "O" => KeyCode::O,
+            "P" => KeyCode::P,
+            "Q" => KeyCode::Q,
+            "R" => KeyCode::R,
+            "S" => KeyCode::S,
+            "T" => KeyCode::T,
+            "U" => KeyCode::U,
+            "V" => KeyCode::V,
+            "W" => KeyCode::W,
+            "X" => KeyCode::X,
+            "Y" => KeyCode::Y,
+            "Z" => KeyCode::Z,
+            _ => return None,
+        };
+        Some(key)
+    }
+    #[cfg(test)]
+    mod tests {
+        use macroquad::math::{vec2, Vec2};
+
+        use super::{closest_point_on_segment, flatten_cubic_bezier};
 
-#[macro_export]
-macro_rules! circle {
-    ($self:ident, $center:expr, $radius:expr, $color:expr) => {
-        $self.colliders.push(StaticBody::new_circle($center, $radius, $color, 0.0));
-    };
-    ($self:ident, $center:expr, $radius:expr, $color:expr, $impact:expr) => {
-        $self.colliders.push(StaticBody::new_circle($center, $radius, $color, $impact));
-    };
+        fn cubic_bezier(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
+            let u = 1.0 - t;
+            p0 * u * u * u + p1 * 3.0 * u * u * t + p2 * 3.0 * u * t * t + p3 * t * t * t
+        }
+
+        fn distance_to_polyline(point: Vec2, polyline: &[Vec2]) -> f32 {
+            polyline.windows(2)
+                .map(|s| closest_point_on_segment(point, s[0], s[1]).distance(point))
+                .fold(f32::INFINITY, f32::min)
+        }
+
+        #[test]
+        fn flattened_bezier_stays_within_tolerance() {
+            let (p0, p1, p2, p3) = (vec2(0.0, 0.0), vec2(0.0, 300.0), vec2(400.0, -200.0), vec2(400.0, 100.0));
+            for tolerance in [0.25, 2.0] {
+                let mut polyline = vec![p0];
+                flatten_cubic_bezier(p0, p1, p2, p3, tolerance, &mut polyline);
+                assert_eq!(polyline.last(), Some(&p3));
+                for i in 0..=1000 {
+                    let point = cubic_bezier(p0, p1, p2, p3, i as f32 / 1000.0);
+                    let distance = distance_to_polyline(point, &polyline);
+                    assert!(distance <= tolerance, "tolerance {tolerance}: curve is {distance} away at t = {}", i as f32 / 1000.0);
+                }
+            }
+        }
+
+        #[test]
+        fn straight_bezier_is_a_single_segment() {
+            let mut polyline = vec![vec2(0.0, 0.0)];
+            flatten_cubic_bezier(vec2(0.0, 0.0), vec2(10.0, 10.0), vec2(20.0, 20.0), vec2(30.0, 30.0), 0.25, &mut polyline);
+            assert_eq!(polyline, [vec2(0.0, 0.0), vec2(30.0, 30.0)]);
+        }
+    }
 }
@@ -0,0 +1,163 @@
+pub mod input {
+    use std::collections::BTreeMap;
+
+    use macroquad::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use crate::helper::helper::key_from_name;
+
+    //Actions that are not flippers
+    pub const ACTION_LAUNCH: &str = "launch";
+    pub const ACTION_RESTART: &str = "restart";
+    pub const ACTION_RESET: &str = "reset";
+
+    //Everything the simulation reads from the player during one physics step.
+    //Held buttons are levels, restart/reset are one-shot presses.
+    #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+    pub struct InputState {
+        //One entry per flipper action, in the order of GameWorld::flipper_actions
+        pub flippers: Vec<bool>,
+        pub launch: bool,
+        //Analog plunger pull from 0 (at rest) to 1 (all the way back), overrides the launch button while above 0
+        #[serde(default)]
+        pub plunger: f32,
+        pub restart: bool,
+        pub reset: bool,
+    }
+
+    impl InputState {
+        //Reads the keyboard, needs a macroquad window
+        pub fn poll(flipper_actions: &[String], bindings: &KeyBindings) -> InputState {
+            let down = |action: &str| bindings.key(action).is_some_and(is_key_down);
+            let pressed = |action: &str| bindings.key(action).is_some_and(is_key_pressed);
+            InputState {
+                flippers: flipper_actions.iter().map(|action| down(action)).collect(),
+                launch: down(ACTION_LAUNCH),
+                plunger: 0.0,
+                restart: pressed(ACTION_RESTART),
+                reset: pressed(ACTION_RESET),
+            }
+        }
+
+        pub fn flipper(&self, action: usize) -> bool {
+            self.flippers.get(action).copied().unwrap_or(false)
+        }
+    }
+
+    //Pixels the mouse has to be dragged down for a full plunger pull
+    const PLUNGER_DRAG_DISTANCE: f32 = 120.0;
+
+    //Analog plunger pull from dragging the mouse down with the left button held
+    #[derive(Default)]
+    pub struct PlungerDrag {
+        //Mouse height where the drag started
+        start: Option<f32>,
+    }
+
+    impl PlungerDrag {
+        //Pull from 0 to 1 for InputState::plunger, needs a macroquad window
+        pub fn update(&mut self) -> f32 {
+            let y = mouse_position().1;
+            if is_mouse_button_pressed(MouseButton::Left) {
+                self.start = Some(y);
+            }
+            if !is_mouse_button_down(MouseButton::Left) {
+                self.start = None;
+            }
+            self.start.map_or(0.0, |start| ((y - start) / PLUNGER_DRAG_DISTANCE).clamp(0.0, 1.0))
+        }
+    }
+
+    //Which key triggers each named action, stored by key name so it can be saved
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(try_from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+    pub struct KeyBindings {
+        keys: BTreeMap<String, KeyCode>,
+    }
+
+    impl Default for KeyBindings {
+        fn default() -> KeyBindings {
+            let mut bindings = KeyBindings { keys: BTreeMap::new() };
+            bindings.bind("left", KeyCode::Left);
+            bindings.bind("right", KeyCode::Right);
+            bindings.bind("upper_left", KeyCode::LeftShift);
+            bindings.bind("upper_right", KeyCode::RightShift);
+            bindings.bind(ACTION_LAUNCH, KeyCode::Space);
+            bindings.bind(ACTION_RESTART, KeyCode::R);
+            bindings.bind(ACTION_RESET, KeyCode::E);
+            bindings
+        }
+    }
+
+    impl KeyBindings {
+        pub fn key(&self, action: &str) -> Option<KeyCode> {
+            self.keys.get(action).copied()
+        }
+
+        //Returns false for keys that could not be written to the settings file
+        pub fn bind(&mut self, action: &str, key: KeyCode) -> bool {
+            if key_from_name(&format!("{key:?}")).is_none() {
+                return false;
+            }
+            self.keys.insert(String::from(action), key);
+            true
+        }
+
+        pub fn unbind(&mut self, action: &str) {
+            self.keys.remove(action);
+        }
+    }
+
+    impl TryFrom<BTreeMap<String, String>> for KeyBindings {
+        type Error = String;
+
+        fn try_from(names: BTreeMap<String, String>) -> Result<KeyBindings, String> {
+            let keys = names.into_iter()
+                .map(|(action, key)| match key_from_name(&key) {
+                    Some(code) => Ok((action, code)),
+                    None => Err(format!("unknown key \"{key}\" for action \"{action}\"")),
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(KeyBindings { keys })
+        }
+    }
+
+    impl From<KeyBindings> for BTreeMap<String, String> {
+        fn from(bindings: KeyBindings) -> BTreeMap<String, String> {
+            //Debug output of KeyCode is the variant name key_from_name expects
+            bindings.keys.into_iter().map(|(action, key)| (action, format!("{key:?}"))).collect()
+        }
+    }
+
+    //Asks for a new key for each action in turn, one key press per action
+    pub struct Rebinding {
+        actions: Vec<String>,
+        current: usize,
+    }
+
+    impl Rebinding {
+        pub fn new(actions: Vec<String>) -> Rebinding {
+            Rebinding { actions, current: 0 }
+        }
+
+        //Action currently waiting for a key, None once every action has been bound
+        pub fn action(&self) -> Option<&str> {
+            self.actions.get(self.current).map(String::as_str)
+        }
+
+        //Reads the last key press, Escape keeps the current binding. Needs a macroquad window
+        pub fn update(&mut self, bindings: &mut KeyBindings) {
+            let Some(action) = self.action() else { return };
+            match get_last_key_pressed() {
+                Some(KeyCode::Escape) => self.current += 1,
+                Some(key) => {
+                    let action = String::from(action);
+                    if bindings.bind(&action, key) {
+                        self.current += 1;
+                    }
+                },
+                None => (),
+            }
+        }
+    }
+}
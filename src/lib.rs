@@ -0,0 +1,18 @@
+//Every module wraps its contents in a module of the same name
+#![allow(clippy::module_inception)]
+
+pub mod broad_phase;
+pub mod collider;
+pub mod events;
+pub mod game_engine;
+pub mod physics_obj;
+pub mod static_obj;
+pub mod table;
+pub mod targets;
+pub mod input;
+pub mod renderer;
+pub mod replay;
+pub mod settings;
+pub mod scoring;
+pub mod sound;
+pub mod helper;
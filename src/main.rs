@@ -1,9 +1,8 @@
+use std::env;
+
 use macroquad::prelude::*;
 
-mod game_engine;
-mod physics_obj;
-mod static_obj;
-pub mod helper;
+use macroquad_experiment::{events::events::EventListener, game_engine::{game_engine::GameWorld, DEFAULT_TABLE}, helper::helper::arg_value, input::input::{InputState, PlungerDrag, Rebinding, ACTION_LAUNCH, ACTION_RESET, ACTION_RESTART}, renderer::renderer::Renderer, replay::replay::Replay, settings::settings::{Settings, SETTINGS_FILE}, sound::sound::SoundBoard};
 
 fn window_config() -> Conf {
     Conf {
@@ -15,14 +14,81 @@ fn window_config() -> Conf {
     }
 }
 
+//Starts asking for a new key for every action
+const REBIND_KEY: KeyCode = KeyCode::F1;
+
+//Usage: macroquad_experiment [--record out.ron | --replay in.ron]
 #[macroquad::main(window_config)]
 async fn main() {
-    let mut game = game_engine::game_engine::GameWorld::create().await;
+    let args: Vec<String> = env::args().collect();
+    let record_path = arg_value(&args, "--record");
+    let replay = arg_value(&args, "--replay")
+        .map(|path| Replay::from_file(&path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}")));
+
+    let table_path = replay.as_ref().map_or(DEFAULT_TABLE, |r| r.table.as_str());
+    let mut game = GameWorld::load(table_path).unwrap_or_else(|e| panic!("Failed to load {table_path}: {e}"));
+    if let Some(replay) = &replay {
+        game.play_replay(replay);
+    }
+    if record_path.is_some() {
+        game.start_recording(table_path);
+        //Recording is saved when the window is closed
+        prevent_quit();
+    }
+
+    let mut settings = Settings::load(SETTINGS_FILE).unwrap_or_else(|e| {
+        eprintln!("Using default settings, {e}");
+        Settings::default()
+    });
+    for action in game.flipper_actions().iter().filter(|a| settings.bindings.key(a).is_none()) {
+        eprintln!("No key bound to flipper action \"{action}\", press F1 to bind keys");
+    }
+    let mut rebinding: Option<Rebinding> = None;
+    let mut plunger_drag = PlungerDrag::default();
+
+    let mut renderer = Renderer::create().await;
+    let mut sounds = SoundBoard::create().await;
 
     loop {
         clear_background(Color::new(0.20, 0.3, 0.5, 1.0));
-        game.physics();
-        game.draw();
+        //The table is paused while keys are being rebound
+        if let Some(current) = &mut rebinding {
+            current.update(&mut settings.bindings);
+            if current.action().is_none() {
+                rebinding = None;
+                if let Err(e) = settings.save(SETTINGS_FILE) {
+                    eprintln!("Failed to save {SETTINGS_FILE}: {e}");
+                }
+            }
+        } else if is_key_pressed(REBIND_KEY) {
+            let mut actions = game.flipper_actions().to_vec();
+            actions.extend([ACTION_LAUNCH, ACTION_RESTART, ACTION_RESET].map(String::from));
+            rebinding = Some(Rebinding::new(actions));
+        } else {
+            let input = InputState {
+                plunger: plunger_drag.update(),
+                ..InputState::poll(game.flipper_actions(), &settings.bindings)
+            };
+            game.physics(get_frame_time(), &input);
+        }
+        for event in game.take_events() {
+            renderer.on_event(&event);
+            sounds.on_event(&event);
+        }
+        renderer.draw(&game);
+        if let Some(action) = rebinding.as_ref().and_then(Rebinding::action) {
+            renderer.draw_prompt(&format!("Press a key for \"{action}\" (Esc keeps the current one)"));
+        }
+
+        if is_quit_requested() {
+            if let (Some(path), Some(recording)) = (&record_path, game.finish_recording()) {
+                match recording.save(path) {
+                    Ok(()) => println!("Saved {} steps to {path}", recording.len()),
+                    Err(e) => eprintln!("Failed to save {path}: {e}"),
+                }
+            }
+            break;
+        }
         next_frame().await;
     }
 }
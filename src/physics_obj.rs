@@ -1,15 +1,61 @@
 pub mod physics_obj {
+    //Restitution between two balls, static bodies use their own material
     const BOUNCINESS: f32 = 0.6;
+    //Most contacts a ball can be stopped at during one physics step
+    const MAX_SUBSTEPS: u32 = 8;
+    //Moment of inertia factor of a solid sphere, I = SPIN_INERTIA * m * r^2
+    const SPIN_INERTIA: f32 = 0.4;
+    //Contacts approaching slower than this count as resting, the ball rolls without slipping on them
+    const ROLLING_SPEED: f32 = 30.0;
+    //How far past the time of impact the ball is moved so the contact registers as overlap
+    const CONTACT_SLOP: f32 = 0.01;
 
     use macroquad::prelude::*;
 
-    use crate::{game_engine::GRAVITY, static_obj::static_obj::StaticBody};
+    use crate::{broad_phase::broad_phase::UniformGrid, collider::collider::ColliderId, game_engine::GRAVITY, static_obj::static_obj::StaticBody};
+
+    //Ball bounced off a collider
+    #[derive(Clone, Copy, Debug)]
+    pub struct Contact {
+        pub body: usize,
+        pub point: Vec2,
+        //Change of the ball's speed along the contact normal
+        pub impulse: f32,
+        //A bumper or slingshot fired on this contact
+        pub kicked: bool,
+    }
+
+    //What is holding a ball in place
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Capture {
+        //Waiting in the shooter lane until the plunger is pulled
+        Launcher,
+        //Caught by a saucer or ball lock
+        Body(ColliderId),
+    }
+
+    //Identity of a ball from being served until it drains, unlike its position in the world's balls
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct BallId(pub u32);
 
     #[derive(Default)]
     pub struct PhysicsBody {
+        //Given out by the world serving the ball
+        pub id: BallId,
         pub position: Vec2,
         pub velocity: Vec2,
         pub radius: f32,
+        //Spin in radians per second (positive is clockwise on screen) and the accumulated angle
+        pub angular_velocity: f32,
+        pub rotation: f32,
+        //Playfield layer, only bodies on the same layer are touched
+        pub layer: i32,
+        //Held balls don't move until released
+        pub captured: Option<Capture>,
+        //Sensors the ball is currently inside
+        pub sensors: Vec<ColliderId>,
+        //Spinner (collider index) the ball was fast enough to pass through when it touched it
+        pub passing_spinner: Option<usize>,
     }
 
     impl PhysicsBody {
@@ -17,18 +63,32 @@ pub mod physics_obj {
         #[allow(dead_code)]
         pub fn empty() -> PhysicsBody {
             PhysicsBody {
+                id: BallId::default(),
                 position: Vec2::new(0.0, 0.0),
                 velocity: Vec2::new(0.0, 0.0),
                 radius: 1.0,
+                angular_velocity: 0.0,
+                rotation: 0.0,
+                layer: 0,
+                captured: None,
+                sensors: Vec::new(),
+                passing_spinner: None,
             }
         }
 
         #[allow(dead_code)]
         pub fn new(position: Vec2, velocity: Vec2, radius: f32) -> PhysicsBody {
             PhysicsBody {
+                id: BallId::default(),
                 position,
                 velocity,
                 radius,
+                angular_velocity: 0.0,
+                rotation: 0.0,
+                layer: 0,
+                captured: None,
+                sensors: Vec::new(),
+                passing_spinner: None,
             }
         }
         #[allow(dead_code)]
@@ -36,20 +96,109 @@ pub mod physics_obj {
             (self.velocity.length_squared() / 2.0) + (bottom_y - self.position.y) * GRAVITY.y
         }
 
-        pub fn update_physics(&mut self, dt: f32, colliders: &mut Vec<StaticBody>, score: &mut f32, debug_draw_points: &mut Vec<(Vec2, i32)>) {
-            #[allow(unused_mut)]
-            let mut acceleration = GRAVITY;
+        pub fn update_physics(&mut self, dt: f32, colliders: &mut [StaticBody], layers: &[i32], broad_phase: &UniformGrid, contacts: &mut Vec<Contact>) {
+            if self.captured.is_some() {
+                return;
+            }
+
+            //Magnets and force zones the ball is in add to gravity
+            let mut candidates = Vec::new();
+            broad_phase.query(self.swept_bounds(Vec2::ZERO), &mut candidates);
+            let acceleration = GRAVITY + candidates.iter()
+                .filter(|&&i| layers[i] == self.layer)
+                .map(|&i| colliders[i].field_acceleration(self.position))
+                .sum::<Vec2>();
 
             self.velocity += acceleration * dt;
-            self.position += self.velocity * dt - 0.5 * acceleration * dt * dt;
+            self.rotation = (self.rotation + self.angular_velocity * dt).rem_euclid(std::f32::consts::TAU);
+            let mut motion = self.velocity * dt - 0.5 * acceleration * dt * dt;
+            let mut remaining_time = dt;
+
+            //Sweep towards the end position, stopping at each contact on the way so fast balls can't skip thin walls
+            for _ in 0..MAX_SUBSTEPS {
+                //Only colliders near the swept path are tested
+                broad_phase.query(self.swept_bounds(motion), &mut candidates);
+                candidates.retain(|&i| layers[i] == self.layer);
+
+                let time_of_impact = candidates.iter()
+                    .filter(|&&i| colliders[i].blocks_sweep(self.velocity, self.passing_spinner == Some(i)))
+                    .filter_map(|&i| colliders[i].time_of_impact(self.position, motion, self.radius))
+                    .min_by(f32::total_cmp);
+
+                let Some(t) = time_of_impact else {
+                    self.position += motion;
+                    self.resolve_collisions(colliders, &candidates, contacts);
+                    return;
+                };
+
+                let t = (t + CONTACT_SLOP / motion.length()).min(1.0);
+                self.position += motion * t;
+                self.resolve_collisions(colliders, &candidates, contacts);
+
+                //Rest of the step continues with the velocity after the bounce
+                remaining_time *= 1.0 - t;
+                motion = self.velocity * remaining_time;
+                if remaining_time <= 0.0 {
+                    return;
+                }
+            }
+        }
+
+        //Equal mass bounce between two balls
+        pub fn collide_with(&mut self, other: &mut PhysicsBody) {
+            if self.layer != other.layer {
+                return;
+            }
+            let displacement = other.position - self.position;
+            let distance = displacement.length();
+            let min_distance = self.radius + other.radius;
+            if distance >= min_distance || distance == 0.0 {
+                return;
+            }
+
+            //Held balls don't budge, a free ball takes the whole push and bounce
+            let self_share = if self.captured.is_some() { 0.0 } else { 1.0 };
+            let other_share = if other.captured.is_some() { 0.0 } else { 1.0 };
+            let total_share = self_share + other_share;
+            if total_share == 0.0 {
+                return;
+            }
+
+            //Balls are pushed apart by their share of the overlap
+            let normal = displacement / distance;
+            let penetration = min_distance - distance;
+            self.position -= normal * penetration * self_share / total_share;
+            other.position += normal * penetration * other_share / total_share;
+
+            let velocity_dot = (other.velocity - self.velocity).dot(normal);
+            if velocity_dot < 0.0 {
+                let impulse = (1.0 + BOUNCINESS) * velocity_dot / total_share;
+
+                self.velocity += impulse * self_share * normal;
+                other.velocity -= impulse * other_share * normal;
+            }
+        }
+
+        //Box around the ball for its whole movement by `motion`
+        pub fn swept_bounds(&self, motion: Vec2) -> Rect {
+            let start = Rect::new(self.position.x - self.radius, self.position.y - self.radius, self.radius * 2.0, self.radius * 2.0);
+            start.combine_with(start.offset(motion))
+        }
+
+        fn resolve_collisions(&mut self, colliders: &mut [StaticBody], candidates: &[usize], contacts: &mut Vec<Contact>) {
+            for &i in candidates {
+                let obj = &mut colliders[i];
+                if obj.spinner_pass(self, self.passing_spinner == Some(i)) {
+                    self.passing_spinner = Some(i);
+                    continue;
+                }
+                if self.passing_spinner == Some(i) {
+                    self.passing_spinner = None;
+                }
 
-            for obj in colliders.iter_mut() {
                 // contact (collision point, collision normal, penetration_depth)
                 let contact = obj.collision_check(self);
                 if let Some(c) = contact {
-                    debug_draw_points.push((c.0, 5));
-                    debug_draw_points.push(((c.1 * c.2) + self.position, 5));
-
                     // Compute relative velocity at contact point
                     let obj_velocity_at_point = match obj {
                         StaticBody::Flipper {
@@ -60,24 +209,174 @@ pub mod physics_obj {
                             let r = c.0 - *origin;
                             Vec2::new(-r.y, r.x) * *angular_velocity
                         },
-                        StaticBody::Circle { impact_force, .. } => {*score += impact_force.floor() * 10.0; c.1 * *impact_force},
-                        StaticBody::Rectangle { impact_force, .. } => {*score += impact_force.floor() * 10.0; c.1 * *impact_force},
+                        StaticBody::Circle { impact_force, .. } => c.1 * *impact_force,
+                        StaticBody::Rectangle { impact_force, .. } => c.1 * *impact_force,
+                        StaticBody::Polygon { impact_force, .. } => c.1 * *impact_force,
+                        StaticBody::Plunger { rotation, speed, .. } => StaticBody::plunger_axis(*rotation) * *speed,
+                        StaticBody::Kinematic { position, velocity, angular_velocity, impact_force, .. } => {
+                            let r = c.0 - *position;
+                            *velocity + Vec2::new(-r.y, r.x) * *angular_velocity + c.1 * *impact_force
+                        },
                         _ => Vec2::ZERO,
                     };
                     
-                    let relative_velocity = self.velocity - obj_velocity_at_point;
+                    //Surface of the spinning ball moves too
+                    let contact_offset = -c.1 * self.radius;
+                    let spin_velocity = vec2(-contact_offset.y, contact_offset.x) * self.angular_velocity;
+
+                    let relative_velocity = self.velocity + spin_velocity - obj_velocity_at_point;
+                    let material = obj.material();
 
                     let velocity_dot = relative_velocity.dot(c.1);
                     if velocity_dot < 0.0 {
-                        let impulse = (1.0 + BOUNCINESS) * velocity_dot;
+                        let impulse = (1.0 + material.restitution) * velocity_dot * obj.impulse_share(c.0, c.1);
+
+                        //Impulse that stops the contact point slipping, split between linear and angular velocity
+                        let tangent_velocity = relative_velocity - velocity_dot * c.1;
+                        let tangent_speed = tangent_velocity.length();
+                        let no_slip_impulse = tangent_speed * SPIN_INERTIA / (1.0 + SPIN_INERTIA);
+
+                        //Resting contacts roll, impacts use Coulomb friction capped by friction * normal impulse
+                        let friction_impulse = if -velocity_dot < ROLLING_SPEED && material.friction > 0.0 {
+                            no_slip_impulse
+                        } else {
+                            (material.friction * impulse.abs()).min(no_slip_impulse)
+                        };
 
                         self.velocity -= impulse * c.1;
+                        if tangent_speed > 0.0 {
+                            let friction = -tangent_velocity / tangent_speed * friction_impulse;
+                            self.velocity += friction;
+                            self.angular_velocity += contact_offset.perp_dot(friction) / (SPIN_INERTIA * self.radius * self.radius);
+                            obj.apply_impulse(c.0, -friction);
+                        }
+                        obj.apply_impulse(c.0, c.1 * impulse);
+
+                        //Bumpers and slingshots add their own kick on top of the bounce
+                        let mut kicked = false;
+                        if let Some(kicker) = obj.kicker_mut() && kicker.try_fire(-velocity_dot) {
+                            self.velocity += c.1 * kicker.impulse;
+                            kicked = true;
+                        }
                         self.position += c.1 * c.2;
+                        contacts.push(Contact { body: i, point: c.0, impulse: -impulse, kicked });
                     }
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::f32::consts::FRAC_PI_2;
+
+        use macroquad::prelude::*;
+
+        use super::PhysicsBody;
+        use crate::{broad_phase::broad_phase::UniformGrid, static_obj::static_obj::{Material, StaticBody}};
+
+        const DT: f32 = 1.0 / 144.0;
+
+        fn simulate(ball: &mut PhysicsBody, bodies: &mut [StaticBody], steps: usize) {
+            let grid = UniformGrid::build(bodies, 64.0);
+            let layers = vec![0; bodies.len()];
+            let mut contacts = Vec::new();
+            for _ in 0..steps {
+                ball.update_physics(DT, bodies, &layers, &grid, &mut contacts);
+            }
+        }
+
+        //Flap across a horizontal approach, passing needs 150 px/s
+        fn spinner() -> StaticBody {
+            StaticBody::new_spinner(vec2(200.0, 200.0), vec2(20.0, 20.0), FRAC_PI_2, 5.0, 150.0, 10.0, GRAY)
+        }
+
+        //Ball rolling at the spinner from the side `speed` points away from, (final x, flap angular velocity)
+        fn run_at_spinner(speed: f32) -> (f32, f32) {
+            let mut bodies = [spinner()];
+            let mut ball = PhysicsBody::new(vec2(200.0 - 40.0 * speed.signum(), 200.0), vec2(speed, 0.0), 10.0);
+            simulate(&mut ball, &mut bodies, 60);
+            let StaticBody::Spinner { angular_velocity, .. } = bodies[0] else { unreachable!() };
+            (ball.position.x, angular_velocity)
+        }
 
-                    //self.position += c.1 * c.2;
+        //Ball coming down at 400 px/s onto a floor of `material` while moving sideways, velocity and spin after the bounce
+        fn bounce_off(material: Material) -> (Vec2, f32) {
+            let mut bodies = [StaticBody::new_rectangle(vec2(200.0, 210.0), vec2(400.0, 20.0), 0.0, GRAY, 0.0).with_material(material)];
+            let mut ball = PhysicsBody::new(vec2(200.0, 185.0), vec2(200.0, 400.0), 10.0);
+            for _ in 0..20 {
+                if ball.velocity.y < 0.0 {
+                    break;
                 }
+                simulate(&mut ball, &mut bodies, 1);
             }
+            (ball.velocity, ball.angular_velocity)
+        }
+
+        #[test]
+        fn fast_ball_does_not_tunnel() {
+            //4 px wall, the ball covers over 40 px per step
+            let mut bodies = [StaticBody::new_rectangle(vec2(200.0, 200.0), vec2(4.0, 400.0), 0.0, GRAY, 0.0)];
+            let mut ball = PhysicsBody::new(vec2(100.0, 200.0), vec2(6000.0, 0.0), 10.0);
+            simulate(&mut ball, &mut bodies, 10);
+            assert!(ball.position.x < 200.0, "ball got through to x = {}", ball.position.x);
+            assert!(ball.velocity.x < 0.0, "ball did not bounce back");
+        }
+
+        #[test]
+        fn material_sets_bounce_and_friction() {
+            for material in [Material::RUBBER, Material::METAL, Material::default()] {
+                let (velocity, _) = bounce_off(material);
+                let restitution = -velocity.y / 400.0;
+                assert!((restitution - material.restitution).abs() < 0.05, "{material:?}: bounced back with {restitution}");
+            }
+
+            let (rubber, rubber_spin) = bounce_off(Material::RUBBER);
+            let (metal, metal_spin) = bounce_off(Material::METAL);
+            let (frictionless, frictionless_spin) = bounce_off(Material::default());
+            assert!(rubber.x < metal.x && metal.x < frictionless.x, "sideways speeds {} {} {}", rubber.x, metal.x, frictionless.x);
+            assert!(rubber_spin.abs() > metal_spin.abs(), "spins {rubber_spin} {metal_spin}");
+            assert_eq!((frictionless.x, frictionless_spin), (200.0, 0.0));
+        }
+
+        #[test]
+        fn sliding_ball_starts_rolling() {
+            let mut bodies = [StaticBody::new_rectangle(vec2(200.0, 210.0), vec2(2000.0, 20.0), 0.0, GRAY, 0.0).with_material(Material::WOOD)];
+            let mut ball = PhysicsBody::new(vec2(200.0, 190.0), vec2(200.0, 0.0), 10.0);
+            simulate(&mut ball, &mut bodies, 144);
+            assert!(ball.velocity.x > 0.0 && ball.velocity.x < 200.0, "sideways speed {}", ball.velocity.x);
+            //Contact point stands still on the floor
+            let slip = ball.velocity.x - ball.angular_velocity * ball.radius;
+            assert!(slip.abs() < 1.0, "ball still slips at {slip} px/s");
+        }
+
+        #[test]
+        fn spinner_blocks_balls_below_min_pass_speed() {
+            for speed in [100.0, 149.0, -100.0, -149.0] {
+                let (x, flap) = run_at_spinner(speed);
+                assert!((x - 200.0) * speed < 0.0, "{speed} px/s: ball got through to x = {x}");
+                assert_eq!(flap, 0.0, "{speed} px/s: flap moved");
+            }
+        }
+
+        #[test]
+        fn spinner_lets_balls_at_min_pass_speed_through() {
+            for speed in [150.0, 152.0, 160.0, 300.0, -152.0, -300.0] {
+                let (x, flap) = run_at_spinner(speed);
+                assert!((x - 200.0) * speed > 0.0, "{speed} px/s: ball was blocked at x = {x}");
+                assert!(flap != 0.0, "{speed} px/s: flap did not spin");
+            }
+        }
+
+        #[test]
+        fn slow_ball_stepping_past_the_flap_is_stopped() {
+            //Only sweeping against the flap catches a ball that moves past it in a single step
+            let mut bodies = [spinner()];
+            let mut ball = PhysicsBody::new(vec2(170.0, 200.0), vec2(140.0, 0.0), 10.0);
+            let grid = UniformGrid::build(&bodies, 64.0);
+            let mut contacts = Vec::new();
+            ball.update_physics(0.5, &mut bodies, &[0], &grid, &mut contacts);
+            assert!(ball.position.x < 200.0, "ball got through to x = {}", ball.position.x);
         }
     }
 }
@@ -0,0 +1,160 @@
+pub mod renderer {
+    use macroquad::prelude::*;
+
+    use crate::{events::events::{EventListener, GameEvent}, game_engine::game_engine::GameWorld, helper::helper::format_number, static_obj::static_obj::shade_color};
+
+    //Seconds a hit flash stays visible
+    const FLASH_TIME: f32 = 0.25;
+    //Hits softer than this don't flash
+    const FLASH_IMPULSE: f32 = 60.0;
+    //Brightness kept for every layer below the playfield
+    const DEPTH_SHADE: f32 = 0.55;
+
+    //Everything that needs a window: font loading and drawing the world
+    pub struct Renderer {
+        font: Option<Font>,
+        //(hit point, seconds left) of recent hits
+        flashes: Vec<(Vec2, f32)>,
+    }
+
+    impl Renderer {
+        pub async fn create() -> Renderer {
+            Renderer {
+                //Load font
+                font: Some(load_ttf_font("sans-medium.ttf").await.expect("No file")),
+                flashes: Vec::new(),
+            }
+        }
+
+        pub fn draw(&mut self, world: &GameWorld) {
+            //Lower layers first so ramps cover the playfield and the playfield covers tunnels
+            let mut layers: Vec<i32> = world.colliders.layers().iter().copied().chain(world.balls.iter().map(|b| b.layer)).collect();
+            layers.sort_unstable();
+            layers.dedup();
+            for layer in layers {
+                let shade = DEPTH_SHADE.powi((-layer).max(0));
+                for ball in world.balls.iter().filter(|b| b.layer == layer) {
+                    //Balls up on a ramp cast a shadow and look closer
+                    let lift = layer.max(0) as f32;
+                    if lift > 0.0 {
+                        draw_circle(ball.position.x + lift * 4.0, ball.position.y + lift * 4.0, ball.radius, Color::new(0.0, 0.0, 0.0, 0.35));
+                    }
+                    let radius = ball.radius * (1.0 + lift * 0.1);
+                    draw_circle(ball.position.x, ball.position.y, radius, shade_color(Color::from_rgba(190, 190, 200, 255), shade));
+
+                    //Highlight turning with the ball so spin is visible
+                    let highlight = ball.position + vec2(ball.rotation.cos(), ball.rotation.sin()) * radius * 0.5;
+                    draw_circle(highlight.x, highlight.y, radius * 0.3, shade_color(Color::from_rgba(235, 235, 245, 255), shade));
+                }
+
+                //Render map
+                for (obj, _) in world.colliders.bodies().iter().zip(world.colliders.layers()).filter(|(_, l)| **l == layer) {
+                    obj.draw_shaded(shade);
+                }
+            }
+
+            //Render hit flashes as fading rings
+            for (point, time_left) in &mut self.flashes {
+                let progress = 1.0 - *time_left / FLASH_TIME;
+                draw_circle_lines(point.x, point.y, 4.0 + progress * 12.0, 2.0, Color::new(1.0, 1.0, 0.8, 1.0 - progress));
+                *time_left -= get_frame_time();
+            }
+            self.flashes.retain(|(_, time_left)| *time_left > 0.0);
+
+            //Render score and lives
+            self.draw_number(format_number((world.score() / 100.0) as i32 * 100), vec2(628.0, 25.0), 12.0, 25.0, 2.0);
+            self.draw_number(world.lives.to_string(), vec2(624.0, 100.0), 15.0, 30.0, 3.0);
+        }
+
+        //Message centered over the table
+        pub fn draw_prompt(&self, text: &str) {
+            let params = TextParams { font: self.font.as_ref(), font_size: 18, color: WHITE, ..Default::default() };
+            let size = measure_text(text, self.font.as_ref(), params.font_size, 1.0);
+            let position = vec2((screen_width() - size.width) / 2.0, screen_height() / 2.0);
+            draw_rectangle(0.0, position.y - size.height - 10.0, screen_width(), size.height + 20.0, Color::new(0.0, 0.0, 0.0, 0.7));
+            draw_text_ex(text, position.x, position.y, params);
+        }
+
+        pub fn draw_number(&self, num: String, corner: Vec2, width: f32, height: f32, thickness: f32) {
+            let mut current_corner = corner;
+        
+            for c in num.chars().rev() {
+                match c {
+                    '0' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE); // top
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE); // bottom
+                        draw_rectangle(current_corner.x, current_corner.y, thickness, height, WHITE); // left
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE); // right
+                    }
+                    '1' => {
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                    }
+                    '2' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE); // top
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE); // middle
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE); // bottom
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height / 2.0, WHITE); // top right
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0, thickness, height / 2.0, WHITE); // bottom left
+                    }
+                    '3' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                    }
+                    '4' => {
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y, thickness, height / 2.0 + thickness / 2.0, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                    }
+                    '5' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y, thickness, height / 2.0, WHITE);
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y + height / 2.0, thickness, height / 2.0, WHITE);
+                    }
+                    '6' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y, thickness, height, WHITE);
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y + height / 2.0, thickness, height / 2.0, WHITE);
+                    }
+                    '7' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                    }
+                    '8' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y, thickness, height, WHITE);
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                    }
+                    '9' => {
+                        draw_rectangle(current_corner.x, current_corner.y, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height / 2.0 - thickness / 2.0, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y + height - thickness, width, thickness, WHITE);
+                        draw_rectangle(current_corner.x + width - thickness, current_corner.y, thickness, height, WHITE);
+                        draw_rectangle(current_corner.x, current_corner.y, thickness, height / 2.0, WHITE);
+                    }
+                    ' ' => {
+                        current_corner += vec2((width + thickness + 3.0) / 1.5, 0.0);
+                    }
+                    _ => {}
+                }
+        
+                current_corner -= vec2(width + thickness + 3.0, 0.0); // spacing between digits
+            }
+        }
+    }
+
+    impl EventListener for Renderer {
+        fn on_event(&mut self, event: &GameEvent) {
+            if let GameEvent::BallHit { point, impulse, .. } = event && *impulse > FLASH_IMPULSE {
+                self.flashes.push((*point, FLASH_TIME));
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+pub mod replay {
+    use std::{fmt, fs, path::Path};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::input::input::InputState;
+
+    #[derive(Debug)]
+    pub enum ReplayError {
+        Io(std::io::Error),
+        Parse(ron::error::SpannedError),
+        Serialize(ron::Error),
+    }
+
+    impl fmt::Display for ReplayError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ReplayError::Io(e) => write!(f, "could not access replay file: {e}"),
+                ReplayError::Parse(e) => write!(f, "malformed replay file: {e}"),
+                ReplayError::Serialize(e) => write!(f, "could not serialize replay: {e}"),
+            }
+        }
+    }
+
+    impl std::error::Error for ReplayError {}
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct BallState {
+        pub position: (f32, f32),
+        pub velocity: (f32, f32),
+    }
+
+    //State at the end of a recording, used to check that playback matches
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct ReplayResult {
+        pub score: f32,
+        pub lives: u32,
+        pub balls: Vec<BallState>,
+    }
+
+    //Input for every physics step since the world was created, run length encoded
+    #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+    pub struct Replay {
+        pub table: String,
+        pub steps: Vec<(u32, InputState)>,
+        #[serde(default)]
+        pub result: Option<ReplayResult>,
+    }
+
+    impl Replay {
+        pub fn new(table: &str) -> Replay {
+            Replay {
+                table: String::from(table),
+                ..Default::default()
+            }
+        }
+
+        pub fn push(&mut self, input: &InputState) {
+            match self.steps.last_mut() {
+                Some((count, last)) if last == input => *count += 1,
+                _ => self.steps.push((1, input.clone())),
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.steps.iter().map(|(count, _)| *count as usize).sum()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.steps.is_empty()
+        }
+
+        //Input of every step in order
+        pub fn inputs(&self) -> impl Iterator<Item = &InputState> {
+            self.steps.iter().flat_map(|(count, input)| std::iter::repeat_n(input, *count as usize))
+        }
+
+        pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Replay, ReplayError> {
+            let source = fs::read_to_string(path).map_err(ReplayError::Io)?;
+            ron::from_str(&source).map_err(ReplayError::Parse)
+        }
+
+        pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ReplayError> {
+            let source = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default().compact_arrays(true)).map_err(ReplayError::Serialize)?;
+            fs::write(path, source).map_err(ReplayError::Io)
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use super::{BallState, Replay, ReplayResult};
+        use crate::input::input::InputState;
+
+        fn input(launch: bool, left: bool) -> InputState {
+            InputState { flippers: vec![left, false], launch, ..Default::default() }
+        }
+
+        #[test]
+        fn repeated_inputs_are_stored_once() {
+            let steps = [input(true, false), input(true, false), input(true, false), input(false, true), input(true, false)];
+            let mut replay = Replay::new("tables/test.ron");
+            for step in &steps {
+                replay.push(step);
+            }
+            assert_eq!(replay.steps.len(), 3);
+            assert_eq!(replay.len(), 5);
+            assert!(replay.inputs().eq(steps.iter()));
+        }
+
+        #[test]
+        fn replay_file_round_trip() {
+            let mut replay = Replay::new("tables/test.ron");
+            for step in 0..100 {
+                replay.push(&InputState { plunger: step as f32 / 100.0, ..input(step < 50, step % 7 == 0) });
+            }
+            replay.result = Some(ReplayResult {
+                score: 12345.0,
+                lives: 2,
+                balls: vec![BallState { position: (1.5, 2.25), velocity: (-3.125, 0.1) }],
+            });
+
+            let path = std::env::temp_dir().join(format!("pinball_replay_{}.ron", std::process::id()));
+            replay.save(&path).unwrap();
+            let loaded = Replay::from_file(&path);
+            std::fs::remove_file(&path).unwrap();
+
+            let loaded = loaded.unwrap();
+            assert_eq!(loaded.table, replay.table);
+            assert_eq!(loaded.steps, replay.steps);
+            assert_eq!(loaded.result, replay.result);
+        }
+    }
+}
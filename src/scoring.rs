@@ -0,0 +1,73 @@
+pub mod scoring {
+    use std::collections::HashMap;
+
+    use crate::{collider::collider::{ColliderId, ColliderSet}, events::events::{EventListener, GameEvent}, game_engine::{SPINNER_HALF_TURN_SCORE, TARGET_SCORE}, static_obj::static_obj::StaticBody};
+
+    //Turns game events into points
+    #[derive(Default)]
+    pub struct ScoreKeeper {
+        score: f32,
+        //Points for hitting each collider
+        hit_points: HashMap<ColliderId, f32>,
+        //Points for every time a bumper or slingshot fires
+        kick_points: HashMap<ColliderId, f32>,
+        //Points for every ball a saucer catches
+        catch_points: HashMap<ColliderId, f32>,
+    }
+
+    impl ScoreKeeper {
+        pub fn new(colliders: &ColliderSet) -> ScoreKeeper {
+            ScoreKeeper {
+                score: 0.0,
+                hit_points: colliders.iter()
+                    .filter_map(|(id, c)| match c {
+                        StaticBody::Circle { impact_force, .. }
+                        | StaticBody::Rectangle { impact_force, .. }
+                        | StaticBody::Polygon { impact_force, .. }
+                        | StaticBody::Kinematic { impact_force, .. } => Some((id, impact_force.floor() * 10.0)),
+                        _ => None,
+                    })
+                    .collect(),
+                kick_points: colliders.iter()
+                    .filter_map(|(id, c)| c.kicker().map(|k| (id, k.score)))
+                    .collect(),
+                catch_points: colliders.iter()
+                    .filter_map(|(id, c)| match c {
+                        StaticBody::Saucer { score, .. } => Some((id, *score)),
+                        _ => None,
+                    })
+                    .collect(),
+            }
+        }
+
+        pub fn score(&self) -> f32 {
+            self.score
+        }
+
+        pub fn reset(&mut self) {
+            self.score = 0.0;
+        }
+    }
+
+    impl EventListener for ScoreKeeper {
+        fn on_event(&mut self, event: &GameEvent) {
+            match event {
+                GameEvent::BallHit { body_id, .. } => {
+                    self.score += self.hit_points.get(body_id).copied().unwrap_or(0.0);
+                },
+                GameEvent::SpinnerRotation { half_turns, .. } => {
+                    self.score += *half_turns as f32 * SPINNER_HALF_TURN_SCORE;
+                },
+                GameEvent::KickerFired { body_id } => {
+                    self.score += self.kick_points.get(body_id).copied().unwrap_or(0.0);
+                },
+                GameEvent::BallCaptured { body_id, .. } => {
+                    self.score += self.catch_points.get(body_id).copied().unwrap_or(0.0);
+                },
+                GameEvent::TargetHit { .. } => self.score += TARGET_SCORE,
+                GameEvent::TargetBankCompleted { bonus, .. } => self.score += bonus,
+                _ => (),
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+pub mod settings {
+    use std::{fmt, fs, io::ErrorKind, path::Path};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::input::input::KeyBindings;
+
+    //Read on startup and written whenever the player changes something
+    pub const SETTINGS_FILE: &str = "settings.ron";
+
+    #[derive(Debug)]
+    pub enum SettingsError {
+        Io(std::io::Error),
+        Parse(ron::error::SpannedError),
+        Serialize(ron::Error),
+    }
+
+    impl fmt::Display for SettingsError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SettingsError::Io(e) => write!(f, "could not access settings file: {e}"),
+                SettingsError::Parse(e) => write!(f, "malformed settings file: {e}"),
+                SettingsError::Serialize(e) => write!(f, "could not serialize settings: {e}"),
+            }
+        }
+    }
+
+    impl std::error::Error for SettingsError {}
+
+    //Player preferences that are kept between sessions, missing fields use defaults
+    #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+    #[serde(default)]
+    pub struct Settings {
+        //Action name -> key name, e.g. "left": "Left"
+        pub bindings: KeyBindings,
+    }
+
+    impl Settings {
+        //A missing file is not an error, it just means nothing was changed yet
+        pub fn load<P: AsRef<Path>>(path: P) -> Result<Settings, SettingsError> {
+            match fs::read_to_string(path) {
+                Ok(source) => ron::from_str(&source).map_err(SettingsError::Parse),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(Settings::default()),
+                Err(e) => Err(SettingsError::Io(e)),
+            }
+        }
+
+        pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), SettingsError> {
+            let source = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map_err(SettingsError::Serialize)?;
+            fs::write(path, source).map_err(SettingsError::Io)
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use std::{fs, path::PathBuf};
+
+        use macroquad::prelude::KeyCode;
+
+        use super::{Settings, SettingsError};
+
+        //Own file per test in the temp directory, tests run in parallel
+        fn temp_file(name: &str) -> PathBuf {
+            std::env::temp_dir().join(format!("pinball_settings_{}_{name}.ron", std::process::id()))
+        }
+
+        #[test]
+        fn settings_survive_a_round_trip() {
+            let mut settings = Settings::default();
+            settings.bindings.bind("left", KeyCode::A);
+            settings.bindings.bind("magna_save", KeyCode::Q);
+            settings.bindings.unbind("upper_right");
+
+            let path = temp_file("round_trip");
+            settings.save(&path).unwrap();
+            let loaded = Settings::load(&path);
+            fs::remove_file(&path).unwrap();
+
+            let loaded = loaded.unwrap();
+            assert_eq!(loaded, settings);
+            assert_eq!(loaded.bindings.key("magna_save"), Some(KeyCode::Q));
+            assert_eq!(loaded.bindings.key("upper_right"), None);
+        }
+
+        #[test]
+        fn missing_file_gives_defaults() {
+            assert_eq!(Settings::load(temp_file("missing")).unwrap(), Settings::default());
+        }
+
+        #[test]
+        fn unknown_key_names_are_rejected() {
+            let path = temp_file("unknown_key");
+            fs::write(&path, r#"(bindings: {"left": "NotAKey"})"#).unwrap();
+            let loaded = Settings::load(&path);
+            fs::remove_file(&path).unwrap();
+            assert!(matches!(loaded, Err(SettingsError::Parse(_))), "{loaded:?}");
+        }
+    }
+}
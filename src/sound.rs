@@ -0,0 +1,100 @@
+pub mod sound {
+    use macroquad::audio::{load_sound_from_bytes, Sound};
+
+    use crate::events::events::{EventListener, GameEvent};
+
+    //Hits softer than this stay silent, resting contacts would click every step otherwise
+    const HIT_SOUND_IMPULSE: f32 = 60.0;
+    //Impulse at which hits play at full volume
+    const HIT_SOUND_FULL_IMPULSE: f32 = 600.0;
+    const SAMPLE_RATE: u32 = 22050;
+
+    //Plays a short generated tone for game events.
+    //Sounds are only audible with the `sound` feature, which needs ALSA development files on Linux.
+    pub struct SoundBoard {
+        hit: Sound,
+        flipper: Sound,
+        launch: Sound,
+        drain: Sound,
+        spinner: Sound,
+        target: Sound,
+        bonus: Sound,
+        kicker: Sound,
+        capture: Sound,
+    }
+
+    impl SoundBoard {
+        pub async fn create() -> SoundBoard {
+            SoundBoard {
+                hit: SoundBoard::load(880.0, 0.06).await,
+                flipper: SoundBoard::load(180.0, 0.05).await,
+                launch: SoundBoard::load(120.0, 0.25).await,
+                drain: SoundBoard::load(90.0, 0.6).await,
+                spinner: SoundBoard::load(1600.0, 0.02).await,
+                target: SoundBoard::load(440.0, 0.08).await,
+                bonus: SoundBoard::load(660.0, 0.4).await,
+                kicker: SoundBoard::load(320.0, 0.1).await,
+                capture: SoundBoard::load(240.0, 0.3).await,
+            }
+        }
+
+        async fn load(frequency: f32, duration: f32) -> Sound {
+            load_sound_from_bytes(&tone_wav(frequency, duration)).await.expect("Generated sound is valid")
+        }
+
+        #[allow(unused_variables)]
+        fn play(sound: &Sound, volume: f32) {
+            #[cfg(feature = "sound")]
+            macroquad::audio::play_sound(sound, macroquad::audio::PlaySoundParams { looped: false, volume });
+        }
+    }
+
+    impl EventListener for SoundBoard {
+        fn on_event(&mut self, event: &GameEvent) {
+            match event {
+                GameEvent::BallHit { impulse, .. } if *impulse > HIT_SOUND_IMPULSE => {
+                    SoundBoard::play(&self.hit, (impulse / HIT_SOUND_FULL_IMPULSE).min(1.0));
+                },
+                GameEvent::FlipperActivated { .. } => SoundBoard::play(&self.flipper, 0.6),
+                GameEvent::BallLaunched { .. } | GameEvent::BallSaved => SoundBoard::play(&self.launch, 0.8),
+                GameEvent::BallDrained { .. } => SoundBoard::play(&self.drain, 1.0),
+                GameEvent::SpinnerRotation { .. } => SoundBoard::play(&self.spinner, 0.4),
+                GameEvent::KickerFired { .. } => SoundBoard::play(&self.kicker, 1.0),
+                GameEvent::TargetHit { .. } => SoundBoard::play(&self.target, 0.7),
+                GameEvent::TargetBankCompleted { .. } | GameEvent::MultiballStarted { .. } | GameEvent::ExtraBallAwarded => SoundBoard::play(&self.bonus, 0.9),
+                GameEvent::BallCaptured { .. } => SoundBoard::play(&self.capture, 0.8),
+                GameEvent::FieldSwitched { enabled: true, .. } => SoundBoard::play(&self.capture, 0.5),
+                GameEvent::BallEjected { .. } => SoundBoard::play(&self.kicker, 1.0),
+                _ => (),
+            }
+        }
+    }
+
+    //Mono 16 bit PCM wav of a sine tone fading out over `duration` seconds
+    fn tone_wav(frequency: f32, duration: f32) -> Vec<u8> {
+        let samples = (SAMPLE_RATE as f32 * duration) as u32;
+        let data_size = samples * 2;
+
+        let mut wav = Vec::with_capacity(44 + data_size as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); //PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); //Mono
+        wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_size.to_le_bytes());
+
+        for i in 0..samples {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let envelope = 1.0 - i as f32 / samples as f32;
+            let sample = (t * frequency * std::f32::consts::TAU).sin() * envelope * 0.5;
+            wav.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+        }
+        wav
+    }
+}
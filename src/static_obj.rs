@@ -5,7 +5,280 @@ pub mod static_obj {
 
     use macroquad::prelude::*;
 
-    use crate::{helper::helper::rotate_vec2, physics_obj::physics_obj::PhysicsBody};
+    use crate::{helper::helper::{catmull_rom_controls, closest_point_on_segment, flatten_cubic_bezier, ray_circle_entry, ray_circle_exit, rotate_vec2}, physics_obj::physics_obj::PhysicsBody};
+
+    //Furthest a flattened spline may stray from the real curve, in pixels
+    const SPLINE_TOLERANCE: f32 = 0.25;
+    //Seconds a sensor keeps glowing after a ball entered it
+    pub const SENSOR_LIT_TIME: f32 = 0.5;
+
+    //Seconds after ejecting before a saucer or lock catches again, so the ejected ball can leave
+    const CATCH_REARM_TIME: f32 = 0.5;
+    //Pulses per second of the overlay drawn over switched on magnets and force zones
+    const FIELD_PULSE_RATE: f32 = 1.5;
+
+    //Color with its brightness scaled, alpha is kept
+    pub fn shade_color(color: Color, shade: f32) -> Color {
+        Color::new(color.r * shade, color.g * shade, color.b * shade, color.a)
+    }
+
+    //Surface response of a body: normal bounce and Coulomb friction coefficient
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Material {
+        pub restitution: f32,
+        pub friction: f32,
+    }
+
+    impl Material {
+        pub const RUBBER: Material = Material { restitution: 0.75, friction: 0.5 };
+        pub const METAL: Material = Material { restitution: 0.5, friction: 0.05 };
+        pub const WOOD: Material = Material { restitution: 0.45, friction: 0.25 };
+        pub const PLASTIC: Material = Material { restitution: 0.6, friction: 0.15 };
+
+        pub fn from_name(name: &str) -> Option<Material> {
+            match name.to_ascii_lowercase().as_str() {
+                "rubber" => Some(Material::RUBBER),
+                "metal" => Some(Material::METAL),
+                "wood" => Some(Material::WOOD),
+                "plastic" => Some(Material::PLASTIC),
+                _ => None,
+            }
+        }
+    }
+
+    //Frictionless with the old global bounciness
+    impl Default for Material {
+        fn default() -> Material {
+            Material { restitution: 0.6, friction: 0.0 }
+        }
+    }
+
+    //How a flipper is driven, torques are relative to the ball's mass like inertia
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct FlipperDrive {
+        //Moment of inertia around the pivot
+        pub inertia: f32,
+        //Torque of the coil while the button is held
+        pub coil_torque: f32,
+        //Torque of the spring pulling the flipper back down
+        pub return_torque: f32,
+        //Share of the angular velocity kept when hitting an end stop
+        pub end_restitution: f32,
+    }
+
+    impl Default for FlipperDrive {
+        fn default() -> FlipperDrive {
+            FlipperDrive { inertia: 2500.0, coil_torque: 250000.0, return_torque: 100000.0, end_restitution: 0.3 }
+        }
+    }
+
+    //Active part of pop bumpers and slingshots that fires the ball back out
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Kicker {
+        //Balls hitting slower than this along the contact normal don't fire it
+        pub min_speed: f32,
+        //Speed added along the contact normal when firing
+        pub impulse: f32,
+        //Seconds before it can fire again
+        pub cooldown: f32,
+        //Points every time it fires
+        pub score: f32,
+        //Seconds of cooldown left, it is drawn lit meanwhile
+        pub timer: f32,
+    }
+
+    impl Kicker {
+        pub fn new(min_speed: f32, impulse: f32, cooldown: f32, score: f32) -> Kicker {
+            Kicker { min_speed, impulse, cooldown, score, timer: 0.0 }
+        }
+
+        //Fires if the ball comes in fast enough and the cooldown is over
+        pub fn try_fire(&mut self, approach_speed: f32) -> bool {
+            if self.timer > 0.0 || approach_speed < self.min_speed {
+                return false;
+            }
+            self.timer = self.cooldown;
+            true
+        }
+
+        //1 right after firing, fading to 0 at the end of the cooldown
+        pub fn glow(&self) -> f32 {
+            if self.cooldown > 0.0 { (self.timer / self.cooldown).clamp(0.0, 1.0) } else { 0.0 }
+        }
+
+        //Color blended towards white while lit
+        fn lit_color(&self, color: Color) -> Color {
+            let glow = self.glow();
+            Color::new(color.r + (1.0 - color.r) * glow, color.g + (1.0 - color.g) * glow, color.b + (1.0 - color.b) * glow, color.a)
+        }
+    }
+
+    //On/off state shared by magnets and force zones, game rules switch it
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Field {
+        pub enabled: bool,
+        //State the table starts in, a timed switch falls back to it
+        resting: bool,
+        //Seconds until the field falls back to its resting state, 0 while it stays as is
+        timer: f32,
+        //Seconds the field has been switched on, drives the pulsing overlay
+        pulse: f32,
+    }
+
+    impl Field {
+        pub fn new(enabled: bool) -> Field {
+            Field { enabled, resting: enabled, timer: 0.0, pulse: 0.0 }
+        }
+
+        //Switches the field, for `duration` seconds or for good if it is 0. Returns whether it changed.
+        pub fn switch(&mut self, enabled: bool, duration: f32) -> bool {
+            let changed = self.enabled != enabled;
+            self.enabled = enabled;
+            self.timer = duration.max(0.0);
+            if changed {
+                self.pulse = 0.0;
+            }
+            changed
+        }
+
+        //Counts down a timed switch, returns the new state if it fell back this step
+        pub fn update(&mut self, dt: f32) -> Option<bool> {
+            if self.enabled {
+                self.pulse += dt;
+            }
+            if self.timer <= 0.0 {
+                return None;
+            }
+            self.timer -= dt;
+            if self.timer > 0.0 {
+                return None;
+            }
+            self.timer = 0.0;
+            let resting = self.resting;
+            self.switch(resting, 0.0).then_some(resting)
+        }
+
+        pub fn reset(&mut self) {
+            *self = Field::new(self.resting);
+        }
+
+        //Overlay opacity from 0 to 1, swelling with every pulse
+        pub fn glow(&self) -> f32 {
+            if !self.enabled {
+                return 0.0;
+            }
+            0.5 - 0.5 * (self.pulse * FIELD_PULSE_RATE * std::f32::consts::TAU).cos()
+        }
+    }
+
+    //Pose of a kinematic body at a point in time
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Keyframe {
+        pub time: f32,
+        pub position: Vec2,
+        pub rotation: f32,
+    }
+
+    //Keyframes a kinematic body moves between in straight lines, plus a steady spin on top
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct KinematicPath {
+        //At least one, in increasing time. Repeat the first at the end for a closed loop.
+        pub keyframes: Vec<Keyframe>,
+        //Runs the keyframes backwards after reaching the last one instead of starting over
+        pub ping_pong: bool,
+        //Radians per second
+        pub spin: f32,
+    }
+
+    impl KinematicPath {
+        //(position, rotation, velocity, angular velocity) `time` seconds in
+        pub fn sample(&self, time: f32) -> (Vec2, f32, Vec2, f32) {
+            let spin = (self.spin * time).rem_euclid(std::f32::consts::TAU);
+            let first = self.keyframes[0];
+            let last = self.keyframes[self.keyframes.len() - 1];
+            if last.time <= 0.0 {
+                return (first.position, first.rotation + spin, Vec2::ZERO, self.spin);
+            }
+
+            //Going backwards flips the velocity
+            let (time, direction) = if self.ping_pong {
+                let time = time.rem_euclid(last.time * 2.0);
+                if time > last.time { (last.time * 2.0 - time, -1.0) } else { (time, 1.0) }
+            } else {
+                (time.rem_euclid(last.time), 1.0)
+            };
+            let Some(segment) = self.keyframes.windows(2).find(|k| time >= k[0].time && time <= k[1].time) else {
+                //Before the first keyframe
+                return (first.position, first.rotation + spin, Vec2::ZERO, self.spin);
+            };
+
+            let (a, b) = (segment[0], segment[1]);
+            let span = b.time - a.time;
+            let t = (time - a.time) / span;
+            (
+                a.position.lerp(b.position, t),
+                a.rotation + (b.rotation - a.rotation) * t + spin,
+                (b.position - a.position) / span * direction,
+                (b.rotation - a.rotation) / span * direction + self.spin,
+            )
+        }
+    }
+
+    //Area covered by a sensor
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum SensorShape {
+        Rectangle { position: Vec2, dimensions: Vec2, rotation: f32 },
+        Circle { position: Vec2, radius: f32 },
+        //Convex, same winding rules as StaticBody::Polygon
+        Polygon { points: Vec<Vec2> },
+    }
+
+    impl SensorShape {
+        //The ball's centre has to be inside, like a rollover switch under the ball
+        pub fn contains(&self, point: Vec2) -> bool {
+            match self {
+                SensorShape::Rectangle { position, dimensions, rotation } => {
+                    let local = rotate_vec2(point - *position, -*rotation).abs();
+                    local.x <= dimensions.x / 2.0 && local.y <= dimensions.y / 2.0
+                },
+                SensorShape::Circle { position, radius } => position.distance_squared(point) <= radius * radius,
+                SensorShape::Polygon { points } => StaticBody::polygon_closest_feature(points, point).2 <= 0.0,
+            }
+        }
+
+        //Polygons get the winding collision code expects
+        fn wound(self) -> SensorShape {
+            match self {
+                SensorShape::Polygon { mut points } => {
+                    if StaticBody::signed_area(&points) < 0.0 {
+                        points.reverse();
+                    }
+                    SensorShape::Polygon { points }
+                },
+                shape => shape,
+            }
+        }
+
+        //Same shape moved from its own frame to `position` and turned by `rotation`
+        fn placed(&self, position: Vec2, rotation: f32) -> SensorShape {
+            match self {
+                SensorShape::Rectangle { position: local, dimensions, rotation: local_rotation } => {
+                    SensorShape::Rectangle { position: position + rotate_vec2(*local, rotation), dimensions: *dimensions, rotation: local_rotation + rotation }
+                },
+                SensorShape::Circle { position: local, radius } => SensorShape::Circle { position: position + rotate_vec2(*local, rotation), radius: *radius },
+                SensorShape::Polygon { points } => SensorShape::Polygon { points: points.iter().map(|p| position + rotate_vec2(*p, rotation)).collect() },
+            }
+        }
+
+        //Solid body of the same shape, for bounds and drawing
+        fn body(&self, color: Color) -> StaticBody {
+            match self {
+                SensorShape::Rectangle { position, dimensions, rotation } => StaticBody::new_rectangle(*position, *dimensions, *rotation, color, 0.0),
+                SensorShape::Circle { position, radius } => StaticBody::new_circle(*position, *radius, color, 0.0),
+                SensorShape::Polygon { points } => StaticBody::new_polygon(points.clone(), color, 0.0),
+            }
+        }
+    }
 
     #[allow(dead_code)]
     #[derive(Default)]
@@ -16,19 +289,46 @@ pub mod static_obj {
             dimensions: Vec2,
             color: Color,
             impact_force: f32,
+            material: Material,
         },
         Circle {
             position: Vec2,
             radius: f32,
             color: Color,
             impact_force: f32,
+            material: Material,
+        },
+        //Convex, points wound so that (edge.y, -edge.x) faces outwards
+        Polygon {
+            points: Vec<Vec2>,
+            color: Color,
+            impact_force: f32,
+            material: Material,
+        },
+        //Capsule around a line, radius 0 is a bare line
+        Segment {
+            start: Vec2,
+            end: Vec2,
+            radius: f32,
+            color: Color,
+            material: Material,
         },
+        //Chain of capsules sharing their end points
+        Polyline {
+            points: Vec<Vec2>,
+            radius: f32,
+            color: Color,
+            material: Material,
+        },
+        //Wall between radius and radius + thickness (thickness may be negative), with rounded ends
         Curve {
             center: Vec2,
             radius: f32,
+            thickness: f32,
             angle_start: f32,
             angle_end: f32,
             render: Vec<StaticBody>,
+            material: Material,
         },
         Flipper {
             origin: Vec2,
@@ -38,15 +338,157 @@ pub mod static_obj {
             rotation_max: f32,
             rotation_min: f32,
             angular_velocity: f32,
+            drive: FlipperDrive,
             color: Color,
+            material: Material,
         },
+        //Flap hanging across a lane, spinning around its long (local x) axis
         Spinner {
             position: Vec2,
             dimensions: Vec2,
             rotation: f32,
-            acc_velocity: f32,
+            angular_velocity: f32,
             top_down_rotation: f32,
+            //Angular deceleration in radians per second squared
+            friction: f32,
+            //Slower balls bounce back off the closed flap
+            min_pass_speed: f32,
+            //Moment of inertia of the flap relative to the ball's mass
+            inertia: f32,
+            color: Color,
+            material: Material,
+        },
+        //Round bumper that kicks the ball away when hit hard enough
+        PopBumper {
+            position: Vec2,
+            radius: f32,
+            kicker: Kicker,
             color: Color,
+            material: Material,
+        },
+        //Rubber capsule between two posts that kicks the ball away from it
+        Slingshot {
+            start: Vec2,
+            end: Vec2,
+            radius: f32,
+            kicker: Kicker,
+            color: Color,
+            material: Material,
+        },
+        //Trigger area that never touches the ball, reports balls entering and leaving
+        Sensor {
+            shape: SensorShape,
+            //Balls inside right now
+            occupants: u32,
+            //Seconds left of the glow after the last ball entered
+            lit: f32,
+            color: Color,
+        },
+        //Ramp entry or exit: moves balls between two layers depending on which way they cross it
+        LayerGate {
+            shape: SensorShape,
+            //Balls on `from` moving along `direction` go to `to`, balls on `to` moving against it go back
+            from: i32,
+            to: i32,
+            direction: Vec2,
+            color: Color,
+        },
+        //Spring loaded head in the shooter lane, pulled back by the player and launching through contact.
+        //Rotation 0 launches towards -y.
+        Plunger {
+            //Center of the head at rest
+            position: Vec2,
+            dimensions: Vec2,
+            rotation: f32,
+            //How far the head can be pulled back
+            travel: f32,
+            //Spring constant per unit of mass, the head reaches travel * sqrt(spring) when released from full travel
+            spring: f32,
+            pull: f32,
+            //Forward speed of the head, negative while being pulled back
+            speed: f32,
+            color: Color,
+            material: Material,
+        },
+        //Pulls balls within `radius` towards its center, strongest there and fading to 0 at the edge.
+        //Negative strength pushes them away instead.
+        Magnet {
+            position: Vec2,
+            radius: f32,
+            //Acceleration at the center
+            strength: f32,
+            //Exponent of the fade, 1 is linear and higher values keep the pull near the center
+            falloff: f32,
+            field: Field,
+            color: Color,
+        },
+        //Accelerates every ball inside its area the same way, for wind and kick zones
+        ForceZone {
+            shape: SensorShape,
+            acceleration: Vec2,
+            field: Field,
+            color: Color,
+        },
+        //Follows a keyframed path and spins, balls touching it are carried along with its surface
+        Kinematic {
+            //Around the body's own origin, at rotation 0
+            shape: SensorShape,
+            path: KinematicPath,
+            //Seconds along the path
+            time: f32,
+            position: Vec2,
+            rotation: f32,
+            velocity: Vec2,
+            angular_velocity: f32,
+            //Shape at the current pose, this is what collides and gets drawn
+            body: Box<StaticBody>,
+            impact_force: f32,
+            color: Color,
+            material: Material,
+        },
+        //Hole that catches slow balls, holds them for `hold_time` and kicks them out with `eject` velocity
+        Saucer {
+            position: Vec2,
+            radius: f32,
+            //Faster balls roll over it
+            max_speed: f32,
+            hold_time: f32,
+            eject: Vec2,
+            //Points for every catch
+            score: f32,
+            holding: bool,
+            //Seconds until the held ball is ejected, or until it can catch again when empty
+            timer: f32,
+            color: Color,
+        },
+        //Holds balls until `capacity` are locked, then releases them all for multiball
+        BallLock {
+            position: Vec2,
+            radius: f32,
+            capacity: u32,
+            eject: Vec2,
+            locked: u32,
+            //Seconds until it can catch again after releasing
+            timer: f32,
+            color: Color,
+        },
+        //Collides until it is hit, then drops below the playfield until its bank resets
+        DropTarget {
+            position: Vec2,
+            dimensions: Vec2,
+            rotation: f32,
+            down: bool,
+            color: Color,
+            material: Material,
+        },
+        //Always collides, stays lit after a hit until its bank resets
+        StandupTarget {
+            position: Vec2,
+            dimensions: Vec2,
+            rotation: f32,
+            lit: bool,
+            color: Color,
+            material: Material,
         },
         #[default]
         Empty 
@@ -60,7 +502,8 @@ pub mod static_obj {
                 rotation, 
                 dimensions, 
                 color,
-                impact_force
+                impact_force,
+                material: Material::default(),
             }
         }
 
@@ -71,16 +514,94 @@ pub mod static_obj {
                 radius, 
                 color,
                 impact_force,
+                material: Material::default(),
             }
         }
         
+        //Points must describe a convex polygon, in either winding order
+        #[allow(dead_code)]
+        pub fn new_polygon(mut points: Vec<Vec2>, color: Color, impact_force: f32) -> StaticBody {
+            if StaticBody::signed_area(&points) < 0.0 {
+                points.reverse();
+            }
+            StaticBody::Polygon {
+                points,
+                color,
+                impact_force,
+                material: Material::default(),
+            }
+        }
+
+        //Twice the signed area, positive for the winding Polygon expects
+        pub fn signed_area(points: &[Vec2]) -> f32 {
+            points.iter().zip(points.iter().cycle().skip(1))
+                .map(|(a, b)| a.perp_dot(*b))
+                .sum()
+        }
+
+        //No reflex corners and a single loop around the inside, collinear points are allowed.
+        //Turning one way at every corner alone would also let self-intersecting stars through.
+        pub fn is_convex(points: &[Vec2]) -> bool {
+            let count = points.len();
+            let turns = (0..count).map(|i| {
+                let a = points[i];
+                let b = points[(i + 1) % count];
+                let c = points[(i + 2) % count];
+                ((b - a).perp_dot(c - b), (b - a).dot(c - b))
+            });
+            let (mut left, mut right) = (false, false);
+            let mut total_turn = 0.0;
+            for (cross, dot) in turns {
+                left |= cross > 0.0;
+                right |= cross < 0.0;
+                total_turn += f32::atan2(cross, dot);
+            }
+            //Exterior angles of a simple polygon add up to one full turn, a pentagram turns twice
+            !(left && right) && (total_turn.abs() - 2.0 * PI).abs() < PI
+        }
+
+        #[allow(dead_code)]
+        pub fn new_segment(start: Vec2, end: Vec2, radius: f32, color: Color) -> StaticBody {
+            StaticBody::Segment { start, end, radius, color, material: Material::default() }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_polyline(points: Vec<Vec2>, radius: f32, color: Color) -> StaticBody {
+            StaticBody::Polyline { points, radius, color, material: Material::default() }
+        }
+
+        //Chain of cubic Bezier spans sharing end points: [start, control, control, end, control, control, end...].
+        //Physics and drawing both use the same flattened Polyline.
+        #[allow(dead_code)]
+        pub fn new_bezier(points: &[Vec2], radius: f32, color: Color) -> StaticBody {
+            let mut flattened = points.first().copied().into_iter().collect();
+            for span in points.windows(4).step_by(3) {
+                flatten_cubic_bezier(span[0], span[1], span[2], span[3], SPLINE_TOLERANCE, &mut flattened);
+            }
+            StaticBody::new_polyline(flattened, radius, color)
+        }
+
+        //Smooth curve through every point, the end points are repeated so it reaches them
+        #[allow(dead_code)]
+        pub fn new_catmull_rom(points: &[Vec2], radius: f32, color: Color) -> StaticBody {
+            let mut flattened = points.first().copied().into_iter().collect();
+            for i in 0..points.len().saturating_sub(1) {
+                let p0 = points[i.saturating_sub(1)];
+                let (p1, p2) = (points[i], points[i + 1]);
+                let p3 = points[(i + 2).min(points.len() - 1)];
+                let (c1, c2) = catmull_rom_controls(p0, p1, p2, p3);
+                flatten_cubic_bezier(p1, c1, c2, p2, SPLINE_TOLERANCE, &mut flattened);
+            }
+            StaticBody::new_polyline(flattened, radius, color)
+        }
+
         #[allow(dead_code)]
         pub fn new_curve(center: Vec2, radius: f32, thickness: f32, angle_start: f32, angle_end: f32, steps: usize, color: Color) -> StaticBody {
-            let mut out = StaticBody::Curve { center, radius, angle_start, angle_end, render: Vec::new() };
+            let mut out = StaticBody::Curve { center, radius, thickness, angle_start, angle_end, render: Vec::new(), material: Material::default() };
 
             let mut angle_step = angle_end - angle_start;
             if angle_step < 0.0 { angle_step += 2.0 * PI; }
-            angle_step = angle_step / steps as f32;
+            angle_step /= steps as f32;
 
             for i in 0..steps {
                 let angle = angle_start + i as f32 * angle_step;
@@ -108,9 +629,24 @@ pub mod static_obj {
                     0.0
                 ));}
             }
+
+            //Rounded caps so both ends look like they collide
+            if steps > 0 && thickness != 0.0 {
+                let (middle, half_thickness) = StaticBody::curve_band(radius, thickness);
+                if let StaticBody::Curve { render, .. } = &mut out {
+                    for angle in [angle_start, angle_end] {
+                        render.push(StaticBody::new_circle(center + vec2(angle.cos(), angle.sin()) * middle, half_thickness, color, 0.0));
+                    }
+                }
+            }
             out
         }
 
+        //(radius of the middle of the wall, half its thickness)
+        fn curve_band(radius: f32, thickness: f32) -> (f32, f32) {
+            (radius + thickness * 0.5, thickness.abs() * 0.5)
+        }
+
         #[allow(dead_code)]
         pub fn new_flipper (origin: Vec2, offset: Vec2, dimensions: Vec2, rotation_min: f32, rotation_max: f32, color: Color) -> StaticBody {
             StaticBody::Flipper { 
@@ -120,27 +656,173 @@ pub mod static_obj {
                 current_rotation: rotation_min, 
                 rotation_max, rotation_min, 
                 angular_velocity: 0.0, 
-                color
+                drive: FlipperDrive::default(),
+                color,
+                material: Material::default(),
+            }
+        }
+
+        pub fn with_drive(mut self, new_drive: FlipperDrive) -> StaticBody {
+            if let StaticBody::Flipper { drive, .. } = &mut self {
+                *drive = new_drive;
+            }
+            self
+        }
+
+        #[allow(dead_code)]
+        pub fn new_spinner(position: Vec2, dimensions: Vec2, rotation: f32, friction: f32, min_pass_speed: f32, inertia: f32, color: Color) -> StaticBody {
+            StaticBody::Spinner {
+                position,
+                dimensions,
+                rotation,
+                angular_velocity: 0.0,
+                top_down_rotation: 0.0,
+                friction,
+                min_pass_speed,
+                inertia,
+                color,
+                material: Material::default(),
+            }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_pop_bumper(position: Vec2, radius: f32, kicker: Kicker, color: Color) -> StaticBody {
+            StaticBody::PopBumper { position, radius, kicker, color, material: Material::default() }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_slingshot(start: Vec2, end: Vec2, radius: f32, kicker: Kicker, color: Color) -> StaticBody {
+            StaticBody::Slingshot { start, end, radius, kicker, color, material: Material::default() }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_sensor(shape: SensorShape, color: Color) -> StaticBody {
+            StaticBody::Sensor { shape: shape.wound(), occupants: 0, lit: 0.0, color }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_layer_gate(shape: SensorShape, from: i32, to: i32, direction: Vec2, color: Color) -> StaticBody {
+            StaticBody::LayerGate { shape: shape.wound(), from, to, direction: direction.normalize_or_zero(), color }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_plunger(position: Vec2, dimensions: Vec2, rotation: f32, travel: f32, spring: f32, color: Color) -> StaticBody {
+            StaticBody::Plunger { position, dimensions, rotation, travel, spring, pull: 0.0, speed: 0.0, color, material: Material::default() }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_magnet(position: Vec2, radius: f32, strength: f32, falloff: f32, enabled: bool, color: Color) -> StaticBody {
+            StaticBody::Magnet { position, radius, strength, falloff, field: Field::new(enabled), color }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_force_zone(shape: SensorShape, acceleration: Vec2, enabled: bool, color: Color) -> StaticBody {
+            StaticBody::ForceZone { shape: shape.wound(), acceleration, field: Field::new(enabled), color }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_kinematic(shape: SensorShape, path: KinematicPath, color: Color, impact_force: f32) -> StaticBody {
+            let shape = shape.wound();
+            let (position, rotation, velocity, angular_velocity) = path.sample(0.0);
+            let body = Box::new(shape.placed(position, rotation).body(color));
+            StaticBody::Kinematic { shape, path, time: 0.0, position, rotation, velocity, angular_velocity, body, impact_force, color, material: Material::default() }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_saucer(position: Vec2, radius: f32, max_speed: f32, hold_time: f32, eject: Vec2, score: f32, color: Color) -> StaticBody {
+            StaticBody::Saucer { position, radius, max_speed, hold_time, eject, score, holding: false, timer: 0.0, color }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_ball_lock(position: Vec2, radius: f32, capacity: u32, eject: Vec2, color: Color) -> StaticBody {
+            StaticBody::BallLock { position, radius, capacity, eject, locked: 0, timer: 0.0, color }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_drop_target(position: Vec2, dimensions: Vec2, rotation: f32, color: Color) -> StaticBody {
+            StaticBody::DropTarget { position, dimensions, rotation, down: false, color, material: Material::default() }
+        }
+
+        #[allow(dead_code)]
+        pub fn new_standup_target(position: Vec2, dimensions: Vec2, rotation: f32, color: Color) -> StaticBody {
+            StaticBody::StandupTarget { position, dimensions, rotation, lit: false, color, material: Material::default() }
+        }
+
+        pub fn with_material(mut self, new_material: Material) -> StaticBody {
+            match &mut self {
+                StaticBody::Rectangle { material, .. }
+                | StaticBody::Circle { material, .. }
+                | StaticBody::Polygon { material, .. }
+                | StaticBody::Segment { material, .. }
+                | StaticBody::Polyline { material, .. }
+                | StaticBody::Curve { material, .. }
+                | StaticBody::Flipper { material, .. }
+                | StaticBody::Spinner { material, .. }
+                | StaticBody::PopBumper { material, .. }
+                | StaticBody::Slingshot { material, .. }
+                | StaticBody::Plunger { material, .. }
+                | StaticBody::Kinematic { material, .. }
+                | StaticBody::DropTarget { material, .. }
+                | StaticBody::StandupTarget { material, .. } => *material = new_material,
+                StaticBody::Sensor { .. } | StaticBody::LayerGate { .. } | StaticBody::Magnet { .. } | StaticBody::ForceZone { .. } | StaticBody::Saucer { .. } | StaticBody::BallLock { .. } | StaticBody::Empty => (),
+            }
+            self
+        }
+
+        pub fn material(&self) -> Material {
+            match self {
+                StaticBody::Rectangle { material, .. }
+                | StaticBody::Circle { material, .. }
+                | StaticBody::Polygon { material, .. }
+                | StaticBody::Segment { material, .. }
+                | StaticBody::Polyline { material, .. }
+                | StaticBody::Curve { material, .. }
+                | StaticBody::Flipper { material, .. }
+                | StaticBody::Spinner { material, .. }
+                | StaticBody::PopBumper { material, .. }
+                | StaticBody::Slingshot { material, .. }
+                | StaticBody::Plunger { material, .. }
+                | StaticBody::Kinematic { material, .. }
+                | StaticBody::DropTarget { material, .. }
+                | StaticBody::StandupTarget { material, .. } => *material,
+                StaticBody::Sensor { .. } | StaticBody::LayerGate { .. } | StaticBody::Magnet { .. } | StaticBody::ForceZone { .. } | StaticBody::Saucer { .. } | StaticBody::BallLock { .. } | StaticBody::Empty => Material::default(),
             }
         }
 
         #[allow(dead_code)]
         pub fn draw(&self) {
+            self.draw_shaded(1.0);
+        }
+
+        //Draws with every color's brightness scaled by `shade`, used for depth between layers
+        pub fn draw_shaded(&self, shade: f32) {
             match self {
                 StaticBody::Rectangle { position, rotation, dimensions: size, color, ..  } => {
                      draw_rectangle_ex(position.x, position.y, size.x, size.y, DrawRectangleParams { 
-                        offset: vec2(0.5, 0.5), rotation: *rotation, color: *color
+                        offset: vec2(0.5, 0.5), rotation: *rotation, color: shade_color(*color, shade)
                     });
                 },
                 StaticBody::Circle { position, radius, color, .. } => {
-                    draw_circle(position.x, position.y, *radius, *color);
+                    draw_circle(position.x, position.y, *radius, shade_color(*color, shade));
+                },
+                StaticBody::Polygon { points, color, .. } => {
+                    //Convex, so a fan from the first point covers it
+                    let vertices = points.iter().map(|p| Vertex::new(p.x, p.y, 0.0, 0.0, 0.0, shade_color(*color, shade))).collect();
+                    let indices = (1..points.len() as u16 - 1).flat_map(|i| [0, i, i + 1]).collect();
+                    draw_mesh(&Mesh { vertices, indices, texture: None });
+                },
+                StaticBody::Segment { start, end, radius, color, .. } => {
+                    StaticBody::draw_capsules(&[*start, *end], *radius, shade_color(*color, shade));
+                },
+                StaticBody::Polyline { points, radius, color, .. } => {
+                    StaticBody::draw_capsules(points, *radius, shade_color(*color, shade));
                 },
                 StaticBody::Curve { render, .. } => {
                     for obj in render {
                         if let StaticBody::Curve {..} = obj {
                             panic!("Why tf is there recursion in draw of Curve???");
                         }
-                        obj.draw();
+                        obj.draw_shaded(shade);
                     }
                 }
                 StaticBody::Flipper { origin, offset, dimensions, current_rotation, color, .. } => {
@@ -148,12 +830,99 @@ pub mod static_obj {
                     let position = *origin + rotated_offset;
 
                     draw_rectangle_ex(position.x, position.y, dimensions.x, dimensions.y, DrawRectangleParams { 
-                        offset: vec2(0.5, 0.5), rotation: *current_rotation, color: *color
+                        offset: vec2(0.5, 0.5), rotation: *current_rotation, color: shade_color(*color, shade)
                     });
                 }
                 StaticBody::Spinner { position, dimensions, rotation, top_down_rotation, color, .. } => {
                     draw_rectangle_ex(position.x, position.y, dimensions.x, dimensions.y * (f32::cos(*top_down_rotation).abs() * 0.9 + 0.1), DrawRectangleParams { 
-                        offset: vec2(0.5, 0.5), rotation: *rotation, color: *color
+                        offset: vec2(0.5, 0.5), rotation: *rotation, color: shade_color(*color, shade)
+                    });
+                }
+                StaticBody::PopBumper { position, radius, kicker, color, .. } => {
+                    draw_circle(position.x, position.y, *radius, kicker.lit_color(shade_color(*color, shade)));
+                    if kicker.glow() > 0.0 {
+                        draw_circle_lines(position.x, position.y, *radius + 3.0, 2.0, Color::new(1.0, 1.0, 0.8, kicker.glow()));
+                    }
+                }
+                StaticBody::Slingshot { start, end, radius, kicker, color, .. } => {
+                    StaticBody::draw_capsules(&[*start, *end], *radius, kicker.lit_color(shade_color(*color, shade)));
+                }
+                StaticBody::Sensor { shape, occupants, lit, color } => {
+                    //Faint until a ball rolls over it
+                    let glow = if *occupants > 0 { 1.0 } else { (*lit / SENSOR_LIT_TIME).clamp(0.0, 1.0) };
+                    shape.body(Color { a: color.a * (0.3 + 0.7 * glow), ..shade_color(*color, shade) }).draw();
+                }
+                StaticBody::LayerGate { shape, color, .. } => shape.body(shade_color(*color, shade)).draw(),
+                StaticBody::Plunger { position, dimensions, rotation, travel, pull, color, .. } => {
+                    //Rod from the back of the lane to the head
+                    let axis = StaticBody::plunger_axis(*rotation);
+                    let head = *position - axis * *pull;
+                    let anchor = *position - axis * (*travel + dimensions.y);
+                    draw_line(head.x, head.y, anchor.x, anchor.y, dimensions.x * 0.3, shade_color(GRAY, shade));
+                    draw_rectangle_ex(head.x, head.y, dimensions.x, dimensions.y, DrawRectangleParams {
+                        offset: vec2(0.5, 0.5), rotation: *rotation, color: shade_color(*color, shade)
+                    });
+                }
+                StaticBody::Kinematic { shape, position, rotation, body, color, .. } => {
+                    body.draw_shaded(shade);
+                    //Round shapes get a stripe so their spin shows
+                    if let SensorShape::Circle { position: local, radius } = shape {
+                        let center = *position + rotate_vec2(*local, *rotation);
+                        let rim = center + rotate_vec2(vec2(*radius * 0.8, 0.0), *rotation);
+                        draw_line(center.x, center.y, rim.x, rim.y, 3.0, shade_color(*color, shade * 0.5));
+                    }
+                }
+                StaticBody::Magnet { position, radius, field, color, .. } => {
+                    //Faint outline while off, rings closing in on the center while on
+                    draw_circle_lines(position.x, position.y, *radius, 1.0, Color { a: color.a * 0.3, ..shade_color(*color, shade) });
+                    if field.enabled {
+                        let glow = field.glow();
+                        draw_circle(position.x, position.y, *radius, Color { a: color.a * 0.25 * glow, ..shade_color(*color, shade) });
+                        let ring = *radius * (1.0 - (field.pulse * FIELD_PULSE_RATE).fract());
+                        draw_circle_lines(position.x, position.y, ring, 2.0, Color { a: color.a * (0.4 + 0.6 * glow), ..shade_color(*color, shade) });
+                    }
+                    draw_circle(position.x, position.y, 4.0, shade_color(*color, shade));
+                }
+                StaticBody::ForceZone { shape, acceleration, field, color } => {
+                    let glow = field.glow();
+                    let alpha = if field.enabled { 0.3 + 0.4 * glow } else { 0.1 };
+                    shape.body(Color { a: color.a * alpha, ..shade_color(*color, shade) }).draw();
+                    //Arrow along the push, from the middle of the area
+                    if field.enabled {
+                        let center = shape.body(BLANK).bounding_box().center();
+                        let tip = center + acceleration.normalize_or_zero() * 15.0;
+                        draw_line(center.x, center.y, tip.x, tip.y, 2.0, Color { a: 0.5 + 0.5 * glow, ..shade_color(*color, shade) });
+                        draw_circle(tip.x, tip.y, 3.0, Color { a: 0.5 + 0.5 * glow, ..shade_color(*color, shade) });
+                    }
+                }
+                StaticBody::Saucer { position, radius, holding, color, .. } => {
+                    //Dark hole with a rim that lights up while holding a ball
+                    draw_circle(position.x, position.y, *radius, shade_color(*color, shade * 0.3));
+                    let rim = if *holding { WHITE } else { *color };
+                    draw_circle_lines(position.x, position.y, *radius, 2.0, shade_color(rim, shade));
+                }
+                StaticBody::BallLock { position, radius, capacity, eject, locked, color, .. } => {
+                    draw_circle(position.x, position.y, *radius, shade_color(*color, shade * 0.3));
+                    draw_circle_lines(position.x, position.y, *radius, 2.0, shade_color(*color, shade));
+                    //One lamp per slot, lit for every locked ball
+                    let forward = eject.normalize_or_zero();
+                    for slot in 0..*capacity {
+                        let lamp = *position - forward * (*radius + 6.0 + slot as f32 * 8.0);
+                        let lamp_color = if slot < *locked { WHITE } else { shade_color(*color, shade * 0.5) };
+                        draw_circle(lamp.x, lamp.y, 3.0, lamp_color);
+                    }
+                }
+                StaticBody::DropTarget { position, dimensions, rotation, down, color, .. } => {
+                    //Dropped targets leave a faint slot behind
+                    let color = if *down { Color { a: color.a * 0.2, ..shade_color(*color, shade) } } else { shade_color(*color, shade) };
+                    draw_rectangle_ex(position.x, position.y, dimensions.x, dimensions.y, DrawRectangleParams {
+                        offset: vec2(0.5, 0.5), rotation: *rotation, color
+                    });
+                }
+                StaticBody::StandupTarget { position, dimensions, rotation, lit, color, .. } => {
+                    let color = shade_color(*color, if *lit { shade } else { shade * 0.4 });
+                    draw_rectangle_ex(position.x, position.y, dimensions.x, dimensions.y, DrawRectangleParams {
+                        offset: vec2(0.5, 0.5), rotation: *rotation, color
                     });
                 }
                 StaticBody::Empty => ()
@@ -196,82 +965,617 @@ pub mod static_obj {
                         None
                     }
                 },
-                StaticBody::Curve { center, radius, render: _, angle_start, angle_end } => {
+                StaticBody::Polygon { points, .. } => {
+                    let (point, normal, distance) = StaticBody::polygon_closest_feature(points, obj.position);
+                    if distance > obj.radius {
+                        return None;
+                    }
+                    Some((point, normal, obj.radius - distance))
+                },
+                StaticBody::Segment { start, end, radius, .. } => StaticBody::capsule_collision(&[*start, *end], *radius, obj),
+                StaticBody::Polyline { points, radius, .. } => StaticBody::capsule_collision(points, *radius, obj),
+                StaticBody::Curve { center, radius, thickness, angle_start, angle_end, .. } => {
+                    let (middle, half_thickness) = StaticBody::curve_band(*radius, *thickness);
                     let displacement: Vec2 = obj.position - *center;
-                    let distance_to_center: f32 = displacement.length();
 
-                    //Exit early if too far or too close
-                    if distance_to_center > *radius + obj.radius || distance_to_center < *radius - obj.radius {
+                    //Closest point on the middle line of the wall, the ends are rounded
+                    let closest = if StaticBody::angle_between(displacement.to_angle(), *angle_start, *angle_end) {
+                        if displacement == Vec2::ZERO { return None; }
+                        *center + displacement.normalize() * middle
+                    } else {
+                        let start: Vec2 = *center + rotate_vec2(vec2(middle, 0.0), *angle_start);
+                        let end: Vec2 = *center + rotate_vec2(vec2(middle, 0.0), *angle_end);
+                        if start.distance_squared(obj.position) < end.distance_squared(obj.position) { start } else { end }
+                    };
+
+                    let offset = obj.position - closest;
+                    let distance = offset.length();
+                    //Centre exactly on the middle line has no way out, same as Circle
+                    if distance > half_thickness + obj.radius || distance == 0.0 {
                         return None;
                     }
+                    let normal = offset / distance;
+                    Some((closest + normal * half_thickness, normal, half_thickness + obj.radius - distance))
+                },
+                StaticBody::PopBumper { position, radius, color, .. } => {
+                    StaticBody::new_circle(*position, *radius, *color, 0.0).collision_check(obj)
+                },
+                StaticBody::Slingshot { start, end, radius, .. } => StaticBody::capsule_collision(&[*start, *end], *radius, obj),
+                StaticBody::DropTarget { down: true, .. } => None,
+                StaticBody::DropTarget { position, dimensions, rotation, color, .. }
+                | StaticBody::StandupTarget { position, dimensions, rotation, color, .. } => {
+                    StaticBody::new_rectangle(*position, *dimensions, *rotation, *color, 0.0).collision_check(obj)
+                },
+                StaticBody::Plunger { position, dimensions, rotation, pull, color, .. } => {
+                    let head = *position - StaticBody::plunger_axis(*rotation) * *pull;
+                    StaticBody::new_rectangle(head, *dimensions, *rotation, *color, 0.0).collision_check(obj)
+                },
+                StaticBody::Sensor { .. } | StaticBody::LayerGate { .. } | StaticBody::Magnet { .. } | StaticBody::ForceZone { .. } | StaticBody::Saucer { .. } | StaticBody::BallLock { .. } => None,
+                StaticBody::Kinematic { body, .. } => body.collision_check(obj),
+                //Solid for balls that don't pass it, see spinner_pass
+                StaticBody::Spinner { position, dimensions, rotation, .. } => {
+                    StaticBody::new_rectangle(*position, *dimensions, *rotation, GRAY, 0.0).collision_check(obj)
+                },
+                StaticBody::Flipper { origin, offset, dimensions, current_rotation, color, .. } => {
+                    let position = *origin + rotate_vec2(*offset, *current_rotation);
 
-                    //Check can it touch inside part or edges the curve
-                    let is_angle_between: bool = {
-                        let angle_to_obj = displacement.to_angle().rem_euclid(std::f32::consts::TAU);
-                        let angle_start = angle_start.rem_euclid(std::f32::consts::TAU);
-                        let angle_end = angle_end.rem_euclid(std::f32::consts::TAU);
+                    StaticBody::new_rectangle(position, *dimensions, *current_rotation, *color, 0.0).collision_check(obj)
+                },
+                StaticBody::Empty => None
+            }
+        }
 
-                        if angle_start < angle_end {
-                            angle_to_obj <= angle_end && angle_to_obj >= angle_start
-                        }
-                        else {
-                            angle_to_obj <= angle_end || angle_to_obj >= angle_start
-                        }
-                    };
-                    
-                    //If object touches arc's inside then
-                    if is_angle_between {
-                        let collision_point: Vec2 = displacement / distance_to_center * *radius + *center;
-                        let collision_point_displacement: Vec2 = obj.position - collision_point;
-                        let penetration: f32 = obj.radius - collision_point_displacement.length();
-
-                        Some((collision_point, collision_point_displacement.normalize(), penetration))
-                    }
-                    //Edges
-                    else {
-                        let start: Vec2 = *center + rotate_vec2(vec2(*radius, 0.0), *angle_start);
-                        let end: Vec2 = *center + rotate_vec2(vec2(*radius, 0.0), *angle_end);
-
-                        let start_distance = start.distance(obj.position);
-                        let end_distance = end.distance(obj.position);
-
-                        if start_distance < end_distance {
-                            let start_displacement = obj.position - start;
-                            if start_displacement.length() < obj.radius {
-                                Some((start, start_displacement.normalize(), obj.radius - start_displacement.length()))
-                            }
-                            else {
-                                None
-                            }
-                        }
-                        else {
-                            let end_displacement = obj.position - end;
-                            if end_displacement.length() < obj.radius {
-                                Some((end, end_displacement.normalize(), obj.radius - end_displacement.length()))
-                            }
-                            else {
-                                None 
-                            }
+        //Axis aligned box around the collision shape (not the rendered one)
+        pub fn bounding_box(&self) -> Rect {
+            match self {
+                StaticBody::Rectangle { position, rotation, dimensions, .. } => StaticBody::rotated_rect_bounds(*position, *dimensions, *rotation),
+                StaticBody::Circle { position, radius, .. } => Rect::new(position.x - radius, position.y - radius, radius * 2.0, radius * 2.0),
+                StaticBody::Polygon { points, .. } => {
+                    let min = points.iter().fold(Vec2::splat(f32::MAX), |m, p| m.min(*p));
+                    let max = points.iter().fold(Vec2::splat(f32::MIN), |m, p| m.max(*p));
+                    Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+                },
+                StaticBody::Segment { start, end, radius, .. } => StaticBody::capsule_bounds(&[*start, *end], *radius),
+                StaticBody::Polyline { points, radius, .. } => StaticBody::capsule_bounds(points, *radius),
+                StaticBody::Curve { center, radius, thickness, angle_start, angle_end, .. } => {
+                    let (middle, half_thickness) = StaticBody::curve_band(*radius, *thickness);
+                    let start = *center + rotate_vec2(vec2(middle, 0.0), *angle_start);
+                    let end = *center + rotate_vec2(vec2(middle, 0.0), *angle_end);
+                    let mut bounds = Rect::new(start.x, start.y, 0.0, 0.0).combine_with(Rect::new(end.x, end.y, 0.0, 0.0));
+
+                    //Arc also reaches every axis extreme it passes through
+                    for quarter in 0..4 {
+                        let angle = quarter as f32 * std::f32::consts::FRAC_PI_2;
+                        if StaticBody::angle_between(angle, *angle_start, *angle_end) {
+                            let extreme = *center + vec2(angle.cos(), angle.sin()) * middle;
+                            bounds = bounds.combine_with(Rect::new(extreme.x, extreme.y, 0.0, 0.0));
                         }
                     }
+                    Rect::new(bounds.x - half_thickness, bounds.y - half_thickness, bounds.w + half_thickness * 2.0, bounds.h + half_thickness * 2.0)
+                },
+                StaticBody::Flipper { origin, offset, dimensions, current_rotation, .. } => {
+                    StaticBody::rotated_rect_bounds(*origin + rotate_vec2(*offset, *current_rotation), *dimensions, *current_rotation)
+                },
+                StaticBody::PopBumper { position, radius, .. } => Rect::new(position.x - radius, position.y - radius, radius * 2.0, radius * 2.0),
+                StaticBody::Slingshot { start, end, radius, .. } => StaticBody::capsule_bounds(&[*start, *end], *radius),
+                StaticBody::Sensor { shape, .. } | StaticBody::LayerGate { shape, .. } | StaticBody::ForceZone { shape, .. } => shape.body(BLANK).bounding_box(),
+                StaticBody::Kinematic { body, .. } => body.bounding_box(),
+                StaticBody::Magnet { position, radius, .. } | StaticBody::Saucer { position, radius, .. } | StaticBody::BallLock { position, radius, .. } => {
+                    Rect::new(position.x - radius, position.y - radius, radius * 2.0, radius * 2.0)
+                },
+                //Whole travel, so the broad phase never has to move it
+                StaticBody::Plunger { position, dimensions, rotation, travel, .. } => {
+                    let pulled = *position - StaticBody::plunger_axis(*rotation) * *travel;
+                    StaticBody::rotated_rect_bounds(*position, *dimensions, *rotation)
+                        .combine_with(StaticBody::rotated_rect_bounds(pulled, *dimensions, *rotation))
                 },
-                StaticBody::Spinner { position, dimensions, rotation, acc_velocity, .. } => {
-                    let collision = StaticBody::new_rectangle(*position, *dimensions, *rotation, GRAY, 0.0).collision_check(obj);
-                    if let Some(_) = collision {
-                        *acc_velocity = rotate_vec2(obj.velocity, -*rotation).y / dimensions.y;
+                StaticBody::Spinner { position, dimensions, rotation, .. }
+                | StaticBody::DropTarget { position, dimensions, rotation, .. }
+                | StaticBody::StandupTarget { position, dimensions, rotation, .. } => StaticBody::rotated_rect_bounds(*position, *dimensions, *rotation),
+                StaticBody::Empty => Rect::default(),
+            }
+        }
+
+        //Fraction of a rigid impact's impulse that reaches a ball hitting at `point` along `normal`.
+        //Bodies that can be pushed around take some of it.
+        pub fn impulse_share(&self, point: Vec2, normal: Vec2) -> f32 {
+            match self {
+                StaticBody::Flipper { origin, drive, .. } => {
+                    let lever = (point - *origin).perp_dot(normal);
+                    1.0 / (1.0 + lever * lever / drive.inertia)
+                },
+                _ => 1.0,
+            }
+        }
+
+        //Applies the reaction of a ball contact, `impulse` is what the body receives
+        pub fn apply_impulse(&mut self, point: Vec2, impulse: Vec2) {
+            if let StaticBody::Flipper { origin, angular_velocity, drive, .. } = self {
+                *angular_velocity += (point - *origin).perp_dot(impulse) / drive.inertia;
+            }
+        }
+
+        //Bodies whose bounding box can change while the game runs
+        pub fn is_moving(&self) -> bool {
+            matches!(self, StaticBody::Flipper { .. } | StaticBody::Spinner { .. } | StaticBody::Kinematic { .. })
+        }
+
+        fn rotated_rect_bounds(center: Vec2, dimensions: Vec2, rotation: f32) -> Rect {
+            let half_size = dimensions / 2.0;
+            let extent = rotate_vec2(half_size, rotation).abs().max(rotate_vec2(vec2(half_size.x, -half_size.y), rotation).abs());
+            Rect::new(center.x - extent.x, center.y - extent.y, extent.x * 2.0, extent.y * 2.0)
+        }
+
+        pub fn kicker(&self) -> Option<&Kicker> {
+            match self {
+                StaticBody::PopBumper { kicker, .. } | StaticBody::Slingshot { kicker, .. } => Some(kicker),
+                _ => None,
+            }
+        }
+
+        pub fn kicker_mut(&mut self) -> Option<&mut Kicker> {
+            match self {
+                StaticBody::PopBumper { kicker, .. } | StaticBody::Slingshot { kicker, .. } => Some(kicker),
+                _ => None,
+            }
+        }
+
+        //Counts down the cooldown of bumpers and slingshots
+        pub fn update_kicker(&mut self, dt: f32) {
+            if let Some(kicker) = self.kicker_mut() {
+                kicker.timer = (kicker.timer - dt).max(0.0);
+            }
+        }
+
+        //Moves kinematic bodies along their path
+        pub fn update_kinematic(&mut self, dt: f32) {
+            if let StaticBody::Kinematic { shape, path, time, position, rotation, velocity, angular_velocity, body, color, .. } = self {
+                *time += dt;
+                (*position, *rotation, *velocity, *angular_velocity) = path.sample(*time);
+                **body = shape.placed(*position, *rotation).body(*color);
+            }
+        }
+
+        //Fades the glow of sensors
+        pub fn update_sensor(&mut self, dt: f32) {
+            if let StaticBody::Sensor { lit, .. } = self {
+                *lit = (*lit - dt).max(0.0);
+            }
+        }
+
+        pub fn field(&self) -> Option<&Field> {
+            match self {
+                StaticBody::Magnet { field, .. } | StaticBody::ForceZone { field, .. } => Some(field),
+                _ => None,
+            }
+        }
+
+        pub fn field_mut(&mut self) -> Option<&mut Field> {
+            match self {
+                StaticBody::Magnet { field, .. } | StaticBody::ForceZone { field, .. } => Some(field),
+                _ => None,
+            }
+        }
+
+        //Acceleration a switched on magnet or force zone adds to a ball at `point`
+        pub fn field_acceleration(&self, point: Vec2) -> Vec2 {
+            match self {
+                StaticBody::Magnet { position, radius, strength, falloff, field, .. } if field.enabled => {
+                    let offset = *position - point;
+                    let distance = offset.length();
+                    if distance >= *radius || distance == 0.0 {
+                        return Vec2::ZERO;
                     }
+                    offset / distance * *strength * (1.0 - distance / *radius).powf(*falloff)
+                },
+                StaticBody::ForceZone { shape, acceleration, field, .. } if field.enabled && shape.contains(point) => *acceleration,
+                _ => Vec2::ZERO,
+            }
+        }
+
+        //Where a saucer or lock would hold this ball if it catches it now, None if the ball rolls on
+        pub fn catch_point(&self, ball: &PhysicsBody) -> Option<Vec2> {
+            match self {
+                StaticBody::Saucer { position, radius, max_speed, holding: false, timer, .. }
+                    if *timer <= 0.0 && ball.position.distance(*position) <= *radius && ball.velocity.length() <= *max_speed => Some(*position),
+                //Locked balls line up behind the entrance, the first one furthest in, so the entrance stays free
+                StaticBody::BallLock { position, radius, capacity, eject, locked, timer, .. }
+                    if *timer <= 0.0 && locked < capacity && ball.position.distance(*position) <= *radius => {
+                    Some(*position - eject.normalize_or_zero() * ball.radius * 2.0 * (capacity - 1 - locked) as f32)
+                },
+                _ => None,
+            }
+        }
+
+        //Takes a ball caught at catch_point
+        pub fn catch_ball(&mut self) {
+            match self {
+                StaticBody::Saucer { hold_time, holding, timer, .. } => {
+                    *holding = true;
+                    *timer = *hold_time;
+                },
+                StaticBody::BallLock { locked, .. } => *locked += 1,
+                _ => (),
+            }
+        }
+
+        //Direction a plunger launches in
+        pub fn plunger_axis(rotation: f32) -> Vec2 {
+            rotate_vec2(vec2(0.0, -1.0), rotation)
+        }
 
+        //Counts down saucers and locks, returns the velocity to eject every held ball with once they let go
+        pub fn update_catcher(&mut self, dt: f32) -> Option<Vec2> {
+            match self {
+                StaticBody::Saucer { eject, holding, timer, .. } => {
+                    *timer = (*timer - dt).max(0.0);
+                    if *holding && *timer <= 0.0 {
+                        *holding = false;
+                        *timer = CATCH_REARM_TIME;
+                        return Some(*eject);
+                    }
                     None
                 },
-                StaticBody::Flipper { origin, offset, dimensions, current_rotation, color, .. } => {
-                    let position = *origin + rotate_vec2(*offset, *current_rotation);
+                StaticBody::BallLock { capacity, eject, locked, timer, .. } => {
+                    *timer = (*timer - dt).max(0.0);
+                    if *locked >= *capacity {
+                        *locked = 0;
+                        *timer = CATCH_REARM_TIME;
+                        return Some(*eject);
+                    }
+                    None
+                },
+                _ => None,
+            }
+        }
 
-                    StaticBody::new_rectangle(position, *dimensions, *current_rotation, *color, 0.0).collision_check(obj)
+        //Empties a saucer or lock without ejecting, for when the balls are removed
+        pub fn reset_catcher(&mut self) {
+            match self {
+                StaticBody::Saucer { holding, timer, .. } => {
+                    *holding = false;
+                    *timer = 0.0;
+                },
+                StaticBody::BallLock { locked, timer, .. } => {
+                    *locked = 0;
+                    *timer = 0.0;
+                },
+                _ => (),
+            }
+        }
+
+        //Registers a ball hit on a target, returns false for other bodies and drop targets that are already down
+        pub fn hit_target(&mut self) -> bool {
+            match self {
+                StaticBody::DropTarget { down, .. } => !std::mem::replace(down, true),
+                StaticBody::StandupTarget { lit, .. } => {
+                    *lit = true;
+                    true
+                },
+                _ => false,
+            }
+        }
+
+        //Dropped or lit, bodies that are not targets never count as hit
+        pub fn is_target_hit(&self) -> bool {
+            matches!(self, StaticBody::DropTarget { down: true, .. } | StaticBody::StandupTarget { lit: true, .. })
+        }
+
+        pub fn reset_target(&mut self) {
+            match self {
+                StaticBody::DropTarget { down, .. } => *down = false,
+                StaticBody::StandupTarget { lit, .. } => *lit = false,
+                _ => (),
+            }
+        }
+
+        //A ball fast enough to pass a spinner pushes the flap around, trading momentum with it
+        //Returns whether the ball passes through the flap, collision_check treats it as solid otherwise.
+        //That is decided from the approach speed on first touch, `passing` keeps a ball going that already got through
+        //even though pushing the flap has slowed it below min_pass_speed.
+        pub fn spinner_pass(&mut self, ball: &mut PhysicsBody, passing: bool) -> bool {
+            let StaticBody::Spinner { position, dimensions, rotation, angular_velocity, min_pass_speed, inertia, .. } = self else {
+                return false;
+            };
+            let local_position = rotate_vec2(ball.position - *position, -*rotation);
+            if StaticBody::rectangle_collision_local(*dimensions, local_position, ball.radius).is_none() {
+                return false;
+            }
+
+            if !passing && !StaticBody::spinner_lets_through(*rotation, *min_pass_speed, ball.velocity) {
+                return false;
+            }
+            let normal = rotate_vec2(vec2(0.0, 1.0), *rotation);
+            let speed = ball.velocity.dot(normal);
+
+            //Ball hits the flap at its tip, it only pushes while moving faster than the tip
+            let lever = dimensions.y * 0.5;
+            let slip = speed - lever * *angular_velocity;
+            if slip * speed > 0.0 {
+                //Impulse after which ball and tip move at the same speed
+                let impulse = slip / (1.0 + lever * lever / *inertia);
+                ball.velocity -= normal * impulse;
+                *angular_velocity += lever * impulse / *inertia;
+            }
+            true
+        }
+
+        //Ball crossing the flap at least at min_pass_speed pushes it round instead of bouncing off
+        fn spinner_lets_through(rotation: f32, min_pass_speed: f32, velocity: Vec2) -> bool {
+            velocity.dot(rotate_vec2(vec2(0.0, 1.0), rotation)).abs() >= min_pass_speed
+        }
+
+        //Whether a sweep with `velocity` has to stop at this body, spinners only stop the balls they block
+        pub fn blocks_sweep(&self, velocity: Vec2, passing: bool) -> bool {
+            match self {
+                StaticBody::Spinner { rotation, min_pass_speed, .. } => {
+                    !passing && !StaticBody::spinner_lets_through(*rotation, *min_pass_speed, velocity)
+                },
+                _ => true,
+            }
+        }
+
+        #[allow(dead_code)]
+        //Returns fraction of `motion` after which a ball starting at `position` first touches the body.
+        //Balls that already overlap at the start return None, those are left to collision_check.
+        pub fn time_of_impact(&self, position: Vec2, motion: Vec2, radius: f32) -> Option<f32> {
+            match self {
+                StaticBody::Circle { position: center, radius: circle_radius, .. } => {
+                    ray_circle_entry(position, motion, *center, *circle_radius + radius)
+                },
+                StaticBody::Rectangle { position: center, rotation, dimensions, .. } => {
+                    StaticBody::rectangle_time_of_impact(*center, *rotation, *dimensions, position, motion, radius)
+                },
+                StaticBody::Polygon { points, .. } => {
+                    if StaticBody::polygon_closest_feature(points, position).2 <= radius {
+                        return None;
+                    }
+
+                    //Swept shape is the polygon grown by the radius: edges pushed out plus a disk on every corner
+                    let mut first_hit: Option<f32> = None;
+                    for i in 0..points.len() {
+                        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                        let edge = b - a;
+                        let normal = vec2(edge.y, -edge.x).normalize();
+                        let distance = (position - a).dot(normal) - radius;
+                        let approach = -motion.dot(normal);
+
+                        let edge_hit = if distance >= 0.0 && approach > 0.0 && distance <= approach {
+                            let t = distance / approach;
+                            let along = (position + motion * t - a).dot(edge) / edge.length_squared();
+                            if (0.0..=1.0).contains(&along) { Some(t) } else { None }
+                        } else {
+                            None
+                        };
+                        let hit = edge_hit.into_iter().chain(ray_circle_entry(position, motion, a, radius)).min_by(f32::total_cmp);
+                        if let Some(t) = hit {
+                            first_hit = Some(first_hit.map_or(t, |f| f.min(t)));
+                        }
+                    }
+                    first_hit
+                },
+                StaticBody::Segment { start, end, radius: capsule_radius, .. } => {
+                    StaticBody::capsule_time_of_impact(&[*start, *end], *capsule_radius + radius, position, motion)
+                },
+                StaticBody::Polyline { points, radius: capsule_radius, .. } => {
+                    StaticBody::capsule_time_of_impact(points, *capsule_radius + radius, position, motion)
+                },
+                StaticBody::Flipper { origin, offset, dimensions, current_rotation, .. } => {
+                    //Flipper is treated as standing still for the duration of the step
+                    let center = *origin + rotate_vec2(*offset, *current_rotation);
+                    StaticBody::rectangle_time_of_impact(center, *current_rotation, *dimensions, position, motion, radius)
+                },
+                StaticBody::Curve { center, radius: curve_radius, thickness, angle_start, angle_end, .. } => {
+                    //Same as a thin arc along the middle of the wall with the ball grown by half the thickness
+                    let (curve_radius, half_thickness) = StaticBody::curve_band(*curve_radius, *thickness);
+                    let radius = radius + half_thickness;
+                    let displacement = position - *center;
+                    let distance = displacement.length();
+                    let start = *center + rotate_vec2(vec2(curve_radius, 0.0), *angle_start);
+                    let end = *center + rotate_vec2(vec2(curve_radius, 0.0), *angle_end);
+
+                    //Already touching the arc or one of its ends
+                    let in_band = (distance - curve_radius).abs() <= radius;
+                    if (in_band && StaticBody::angle_between(displacement.to_angle(), *angle_start, *angle_end))
+                        || start.distance(position) <= radius || end.distance(position) <= radius {
+                        return None;
+                    }
+
+                    //Swept shape of a thin arc is a ring sector plus a disk at each end
+                    let outer = ray_circle_entry(position, motion, *center, curve_radius + radius);
+                    let inner = if distance < curve_radius - radius {
+                        ray_circle_exit(position, motion, *center, curve_radius - radius)
+                    } else {
+                        None
+                    };
+                    let ring_hit = [outer, inner].into_iter().flatten()
+                        .filter(|t| StaticBody::angle_between((position + motion * *t - *center).to_angle(), *angle_start, *angle_end));
+
+                    ring_hit
+                        .chain(ray_circle_entry(position, motion, start, radius))
+                        .chain(ray_circle_entry(position, motion, end, radius))
+                        .min_by(f32::total_cmp)
+                },
+                //Flap standing still for the step, balls fast enough to pass are left out by blocks_sweep
+                StaticBody::Spinner { position: center, dimensions, rotation, .. } => {
+                    StaticBody::rectangle_time_of_impact(*center, *rotation, *dimensions, position, motion, radius)
+                },
+                StaticBody::Sensor { .. } | StaticBody::LayerGate { .. } | StaticBody::Magnet { .. } | StaticBody::ForceZone { .. } | StaticBody::Saucer { .. } | StaticBody::BallLock { .. } => None,
+                StaticBody::Kinematic { body, .. } => body.time_of_impact(position, motion, radius),
+                StaticBody::PopBumper { position: center, radius: bumper_radius, .. } => {
+                    ray_circle_entry(position, motion, *center, *bumper_radius + radius)
+                },
+                StaticBody::Slingshot { start, end, radius: capsule_radius, .. } => {
+                    StaticBody::capsule_time_of_impact(&[*start, *end], *capsule_radius + radius, position, motion)
+                },
+                StaticBody::DropTarget { down: true, .. } => None,
+                StaticBody::DropTarget { position: center, rotation, dimensions, .. }
+                | StaticBody::StandupTarget { position: center, rotation, dimensions, .. } => {
+                    StaticBody::rectangle_time_of_impact(*center, *rotation, *dimensions, position, motion, radius)
+                },
+                StaticBody::Plunger { position: rest, rotation, dimensions, pull, .. } => {
+                    let head = *rest - StaticBody::plunger_axis(*rotation) * *pull;
+                    StaticBody::rectangle_time_of_impact(head, *rotation, *dimensions, position, motion, radius)
                 },
                 StaticBody::Empty => None
             }
         }
 
+        fn rectangle_time_of_impact(center: Vec2, rotation: f32, dimensions: Vec2, position: Vec2, motion: Vec2, radius: f32) -> Option<f32> {
+            let local_position = rotate_vec2(position - center, -rotation);
+            if StaticBody::rectangle_collision_local(dimensions, local_position, radius).is_some() {
+                return None;
+            }
+            let local_motion = rotate_vec2(motion, -rotation);
+            let half_size = dimensions / 2.0;
+            let expanded = half_size + Vec2::splat(radius);
+
+            //Slab test against the box grown by the ball radius
+            let mut t_min: f32 = 0.0;
+            let mut t_max: f32 = 1.0;
+            for axis in 0..2 {
+                if local_motion[axis] == 0.0 {
+                    if local_position[axis].abs() > expanded[axis] {
+                        return None;
+                    }
+                    continue;
+                }
+                let t1 = (-expanded[axis] - local_position[axis]) / local_motion[axis];
+                let t2 = (expanded[axis] - local_position[axis]) / local_motion[axis];
+                t_min = t_min.max(t1.min(t2));
+                t_max = t_max.min(t1.max(t2));
+                if t_min > t_max {
+                    return None;
+                }
+            }
+
+            //Entering through a corner of the grown box, the real shape is rounded there
+            let hit = local_position + local_motion * t_min;
+            if hit.x.abs() > half_size.x && hit.y.abs() > half_size.y {
+                return ray_circle_entry(local_position, local_motion, half_size * hit.signum(), radius);
+            }
+            Some(t_min)
+        }
+
+        //Contact with the closest of the capsules along `points`
+        fn capsule_collision(points: &[Vec2], radius: f32, obj: &PhysicsBody) -> Option<(Vec2, Vec2, f32)> {
+            let closest = points.windows(2)
+                .map(|pair| closest_point_on_segment(obj.position, pair[0], pair[1]))
+                .min_by(|a, b| a.distance_squared(obj.position).total_cmp(&b.distance_squared(obj.position)))?;
+            let displacement = obj.position - closest;
+            let distance = displacement.length();
+            //Centre exactly on the line has no way out, same as Circle
+            if distance > radius + obj.radius || distance == 0.0 {
+                return None;
+            }
+            let normal = displacement / distance;
+            Some((closest + normal * radius, normal, radius + obj.radius - distance))
+        }
+
+        //`radius` is the capsule's plus the ball's
+        fn capsule_time_of_impact(points: &[Vec2], radius: f32, position: Vec2, motion: Vec2) -> Option<f32> {
+            let overlapping = points.windows(2)
+                .any(|pair| closest_point_on_segment(position, pair[0], pair[1]).distance(position) <= radius);
+            if overlapping {
+                return None;
+            }
+
+            //Each capsule is two lines pushed out by the radius plus a disk on both ends
+            let mut first_hit: Option<f32> = None;
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let edge = b - a;
+                if edge.length_squared() == 0.0 {
+                    continue;
+                }
+                let mut normal = vec2(edge.y, -edge.x).normalize();
+                if (position - a).dot(normal) < 0.0 {
+                    normal = -normal;
+                }
+
+                let distance = (position - a).dot(normal) - radius;
+                let approach = -motion.dot(normal);
+                let side_hit = if distance >= 0.0 && approach > 0.0 && distance <= approach {
+                    let t = distance / approach;
+                    let along = (position + motion * t - a).dot(edge) / edge.length_squared();
+                    if (0.0..=1.0).contains(&along) { Some(t) } else { None }
+                } else {
+                    None
+                };
+                let hit = side_hit.into_iter()
+                    .chain(ray_circle_entry(position, motion, a, radius))
+                    .chain(ray_circle_entry(position, motion, b, radius))
+                    .min_by(f32::total_cmp);
+                if let Some(t) = hit {
+                    first_hit = Some(first_hit.map_or(t, |f| f.min(t)));
+                }
+            }
+            first_hit
+        }
+
+        fn capsule_bounds(points: &[Vec2], radius: f32) -> Rect {
+            let min = points.iter().fold(Vec2::splat(f32::MAX), |m, p| m.min(*p)) - Vec2::splat(radius);
+            let max = points.iter().fold(Vec2::splat(f32::MIN), |m, p| m.max(*p)) + Vec2::splat(radius);
+            Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+        }
+
+        //Thick lines with round joints and ends
+        fn draw_capsules(points: &[Vec2], radius: f32, color: Color) {
+            //Bare lines still need to be visible
+            let thickness = f32::max(radius * 2.0, 1.0);
+            for pair in points.windows(2) {
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, thickness, color);
+            }
+            for point in points {
+                draw_circle(point.x, point.y, thickness / 2.0, color);
+            }
+        }
+
+        //(closest point on the outline, outward normal, signed distance) for a point, negative inside
+        fn polygon_closest_feature(points: &[Vec2], position: Vec2) -> (Vec2, Vec2, f32) {
+            //Edge the point is furthest outside of, or least inside of
+            let mut deepest = (f32::MIN, Vec2::ZERO);
+            for i in 0..points.len() {
+                let edge = points[(i + 1) % points.len()] - points[i];
+                let normal = vec2(edge.y, -edge.x).normalize();
+                let distance = (position - points[i]).dot(normal);
+                if distance > deepest.0 {
+                    deepest = (distance, normal);
+                }
+            }
+            let (distance, normal) = deepest;
+
+            //Inside, the nearest way out is straight through that edge
+            if distance <= 0.0 {
+                return (position - normal * distance, normal, distance);
+            }
+
+            //Outside, the closest feature may be an edge or a corner
+            let closest = (0..points.len())
+                .map(|i| closest_point_on_segment(position, points[i], points[(i + 1) % points.len()]))
+                .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+                .unwrap_or(position);
+            let displacement = position - closest;
+            let distance = displacement.length();
+            if distance == 0.0 {
+                return (closest, normal, 0.0);
+            }
+            (closest, displacement / distance, distance)
+        }
+
+        fn angle_between(angle: f32, angle_start: f32, angle_end: f32) -> bool {
+            let angle = angle.rem_euclid(std::f32::consts::TAU);
+            let angle_start = angle_start.rem_euclid(std::f32::consts::TAU);
+            let angle_end = angle_end.rem_euclid(std::f32::consts::TAU);
+
+            if angle_start < angle_end {
+                angle <= angle_end && angle >= angle_start
+            }
+            else {
+                angle <= angle_end || angle >= angle_start
+            }
+        }
+
         fn rectangle_collision_local(dimensions: Vec2, obj_position: Vec2, obj_radius: f32) -> Option<(Vec2, Vec2, f32)> {
             
             let displacement: Vec2 = obj_position;
@@ -317,4 +1621,264 @@ pub mod static_obj {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[cfg(test)]
+    mod tests {
+        use std::f32::consts::{PI, TAU};
+
+        use macroquad::prelude::*;
+
+        use super::{Field, Keyframe, Kicker, KinematicPath, SensorShape, StaticBody};
+        use crate::physics_obj::physics_obj::PhysicsBody;
+
+        //Thin walls standing across x = 200 from y = 0 to 400
+        fn walls() -> Vec<(&'static str, StaticBody)> {
+            vec![
+                ("rectangle", StaticBody::new_rectangle(vec2(200.0, 200.0), vec2(4.0, 400.0), 0.0, GRAY, 0.0)),
+                ("circle", StaticBody::new_circle(vec2(230.0, 200.0), 30.0, GRAY, 0.0)),
+                ("curve", StaticBody::new_curve(vec2(100.0, 200.0), 98.0, 4.0, -1.0, 1.0, 0, GRAY)),
+            ]
+        }
+
+        #[test]
+        fn sweep_stops_at_the_wall_surface() {
+            for (name, wall) in walls() {
+                let t = wall.time_of_impact(vec2(100.0, 200.0), vec2(400.0, 0.0), 10.0)
+                    .unwrap_or_else(|| panic!("{name}: sweep missed the wall"));
+                let front = 100.0 + 400.0 * t + 10.0;
+                assert!((196.0..=202.0).contains(&front), "{name}: ball front stops at x = {front}");
+            }
+        }
+
+        #[test]
+        fn sweep_away_from_the_wall_misses() {
+            for (name, wall) in walls() {
+                assert_eq!(wall.time_of_impact(vec2(100.0, 200.0), vec2(-400.0, 0.0), 10.0), None, "{name}");
+            }
+        }
+
+        #[test]
+        fn convex_polygons_are_accepted() {
+            let square = [vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 10.0), vec2(0.0, 10.0)];
+            let reversed: Vec<Vec2> = square.iter().rev().copied().collect();
+            let with_collinear_point = [vec2(0.0, 0.0), vec2(5.0, 0.0), vec2(10.0, 0.0), vec2(5.0, 5.0)];
+            assert!(StaticBody::is_convex(&square));
+            assert!(StaticBody::is_convex(&reversed));
+            assert!(StaticBody::is_convex(&with_collinear_point));
+        }
+
+        #[test]
+        fn non_convex_polygons_are_rejected() {
+            let arrow = [vec2(0.0, 0.0), vec2(10.0, 5.0), vec2(0.0, 10.0), vec2(4.0, 5.0)];
+            let pentagram: Vec<Vec2> = (0..5).map(|i| {
+                let angle = i as f32 * 4.0 * PI / 5.0;
+                vec2(angle.cos(), angle.sin()) * 10.0
+            }).collect();
+            assert!(!StaticBody::is_convex(&arrow));
+            assert!(!StaticBody::is_convex(&pentagram));
+        }
+
+        #[test]
+        fn polygon_pushes_ball_out_of_its_nearest_edge() {
+            let mut square = StaticBody::new_polygon(vec![vec2(0.0, 0.0), vec2(100.0, 0.0), vec2(100.0, 100.0), vec2(0.0, 100.0)], GRAY, 0.0);
+            let ball = PhysicsBody::new(vec2(50.0, 106.0), Vec2::ZERO, 10.0);
+            let (_, normal, penetration) = square.collision_check(&ball).expect("ball overlaps the bottom edge");
+            assert!(normal.abs_diff_eq(vec2(0.0, 1.0), 1e-5), "normal {normal}");
+            assert!((penetration - 4.0).abs() < 1e-4, "penetration {penetration}");
+            assert_eq!(square.collision_check(&PhysicsBody::new(vec2(50.0, 111.0), Vec2::ZERO, 10.0)), None);
+        }
+
+        #[test]
+        fn splines_are_swept_like_walls() {
+            let bezier = StaticBody::new_bezier(&[vec2(200.0, 0.0), vec2(200.0, 100.0), vec2(200.0, 300.0), vec2(200.0, 400.0)], 1.0, GRAY);
+            let catmull_rom = StaticBody::new_catmull_rom(&[vec2(200.0, 0.0), vec2(202.0, 200.0), vec2(200.0, 400.0)], 1.0, GRAY);
+            for (name, wall) in [("bezier", bezier), ("catmull_rom", catmull_rom)] {
+                let t = wall.time_of_impact(vec2(100.0, 200.0), vec2(400.0, 0.0), 10.0)
+                    .unwrap_or_else(|| panic!("{name}: sweep missed the rail"));
+                let front = 100.0 + 400.0 * t + 10.0;
+                assert!((198.0..=202.0).contains(&front), "{name}: ball front stops at x = {front}");
+            }
+        }
+
+        #[test]
+        fn catmull_rom_passes_through_every_point() {
+            let points = [vec2(0.0, 0.0), vec2(100.0, 50.0), vec2(150.0, 200.0), vec2(50.0, 250.0)];
+            let StaticBody::Polyline { points: flattened, .. } = StaticBody::new_catmull_rom(&points, 1.0, GRAY) else {
+                panic!("Catmull-Rom rail is not a polyline");
+            };
+            for point in points {
+                assert!(flattened.contains(&point), "{point} is not on the rail");
+            }
+            assert_eq!((flattened.first(), flattened.last()), (points.first(), points.last()));
+        }
+
+        #[test]
+        fn thick_curve_collides_on_both_surfaces_and_its_end_caps() {
+            //Wall from radius 100 to 120 over the quarter from angle 0 to PI / 2
+            let mut curve = StaticBody::new_curve(Vec2::ZERO, 100.0, 20.0, 0.0, PI / 2.0, 0, GRAY);
+            let diagonal = Vec2::from_angle(PI / 4.0);
+            let mut contact = |position: Vec2| curve.collision_check(&PhysicsBody::new(position, Vec2::ZERO, 10.0));
+
+            for (distance, expected_normal) in [(128.0, diagonal), (92.0, -diagonal)] {
+                let (point, normal, penetration) = contact(diagonal * distance).unwrap_or_else(|| panic!("no contact at {distance}"));
+                assert!(normal.abs_diff_eq(expected_normal, 1e-4), "{distance}: normal {normal}");
+                assert!((penetration - 2.0).abs() < 1e-3, "{distance}: penetration {penetration}");
+                assert!((point.length() - if distance > 110.0 { 120.0 } else { 100.0 }).abs() < 1e-3, "{distance}: contact at {point}");
+            }
+            assert_eq!(contact(diagonal * 131.0), None);
+            assert_eq!(contact(diagonal * 89.0), None);
+
+            //Past the end at angle PI / 2 the ball meets the rounded cap around (0, 110)
+            let (point, normal, penetration) = contact(vec2(-15.0, 110.0)).expect("no contact with the end cap");
+            assert!(normal.abs_diff_eq(vec2(-1.0, 0.0), 1e-4), "cap normal {normal}");
+            assert!((penetration - 5.0).abs() < 1e-3, "cap penetration {penetration}");
+            assert!(point.abs_diff_eq(vec2(-10.0, 110.0), 1e-3), "cap contact at {point}");
+            assert_eq!(contact(vec2(-25.0, 110.0)), None);
+        }
+
+        #[test]
+        fn rails_collide_as_capsules() {
+            let segment = || StaticBody::new_segment(vec2(0.0, 0.0), vec2(100.0, 0.0), 5.0, GRAY);
+            let polyline = StaticBody::new_polyline(vec![vec2(0.0, 0.0), vec2(100.0, 0.0), vec2(100.0, 100.0)], 5.0, GRAY);
+            let cases = [
+                //Side, rounded end and the outside of the polyline's corner
+                (segment(), vec2(50.0, 12.0), vec2(0.0, 1.0), 3.0),
+                (segment(), vec2(110.0, 0.0), vec2(1.0, 0.0), 5.0),
+                (polyline, vec2(110.0, -6.0), vec2(10.0, -6.0).normalize(), 15.0 - 136.0f32.sqrt()),
+            ];
+            for (mut rail, position, expected_normal, expected_penetration) in cases {
+                let (_, normal, penetration) = rail.collision_check(&PhysicsBody::new(position, Vec2::ZERO, 10.0))
+                    .unwrap_or_else(|| panic!("no contact at {position}"));
+                assert!(normal.abs_diff_eq(expected_normal, 1e-4), "{position}: normal {normal}");
+                assert!((penetration - expected_penetration).abs() < 1e-3, "{position}: penetration {penetration}");
+            }
+            assert_eq!(segment().collision_check(&PhysicsBody::new(vec2(50.0, 16.0), Vec2::ZERO, 10.0)), None);
+        }
+
+        #[test]
+        fn kicker_fires_above_min_speed_once_per_cooldown() {
+            let mut bumper = StaticBody::new_pop_bumper(Vec2::ZERO, 15.0, Kicker::new(40.0, 250.0, 0.15, 1000.0), WHITE);
+            let try_fire = |bumper: &mut StaticBody, speed| bumper.kicker_mut().unwrap().try_fire(speed);
+
+            assert!(!try_fire(&mut bumper, 39.0));
+            assert!(try_fire(&mut bumper, 40.0));
+            assert_eq!(bumper.kicker_mut().unwrap().glow(), 1.0);
+            assert!(!try_fire(&mut bumper, 500.0), "fired during the cooldown");
+
+            bumper.update_kicker(0.1);
+            assert!(!try_fire(&mut bumper, 500.0), "fired during the cooldown");
+            bumper.update_kicker(0.06);
+            assert_eq!(bumper.kicker_mut().unwrap().glow(), 0.0);
+            assert!(try_fire(&mut bumper, 500.0));
+        }
+
+        #[test]
+        fn sensor_shapes_contain_the_ball_centre() {
+            let rectangle = SensorShape::Rectangle { position: vec2(100.0, 100.0), dimensions: vec2(40.0, 10.0), rotation: PI / 2.0 };
+            let circle = SensorShape::Circle { position: vec2(100.0, 100.0), radius: 10.0 };
+            let polygon = SensorShape::Polygon { points: vec![vec2(90.0, 90.0), vec2(110.0, 90.0), vec2(100.0, 110.0)] };
+            for (shape, inside, outside) in [
+                (rectangle, vec2(100.0, 118.0), vec2(118.0, 100.0)),
+                (circle, vec2(107.0, 107.0), vec2(108.0, 108.0)),
+                (polygon, vec2(100.0, 105.0), vec2(107.0, 105.0)),
+            ] {
+                assert!(shape.contains(inside), "{shape:?} should contain {inside}");
+                assert!(!shape.contains(outside), "{shape:?} should not contain {outside}");
+            }
+        }
+
+        #[test]
+        fn magnet_pulls_towards_its_centre_only_while_switched_on() {
+            let mut magnet = StaticBody::new_magnet(vec2(100.0, 100.0), 40.0, 1000.0, 2.0, false, GRAY);
+            assert_eq!(magnet.field_acceleration(vec2(120.0, 100.0)), Vec2::ZERO);
+
+            magnet.field_mut().unwrap().switch(true, 0.0);
+            //Halfway out, (1 - 0.5)^2 of the full strength
+            assert!(magnet.field_acceleration(vec2(120.0, 100.0)).abs_diff_eq(vec2(-250.0, 0.0), 1e-3));
+            assert!(magnet.field_acceleration(vec2(100.0, 80.0)).abs_diff_eq(vec2(0.0, 250.0), 1e-3));
+            assert_eq!(magnet.field_acceleration(vec2(141.0, 100.0)), Vec2::ZERO);
+        }
+
+        #[test]
+        fn force_zone_pushes_inside_its_shape() {
+            let shape = SensorShape::Circle { position: vec2(100.0, 100.0), radius: 20.0 };
+            let mut zone = StaticBody::new_force_zone(shape, vec2(0.0, -800.0), true, GRAY);
+            assert_eq!(zone.field_acceleration(vec2(110.0, 100.0)), vec2(0.0, -800.0));
+            assert_eq!(zone.field_acceleration(vec2(130.0, 100.0)), Vec2::ZERO);
+            zone.field_mut().unwrap().switch(false, 0.0);
+            assert_eq!(zone.field_acceleration(vec2(110.0, 100.0)), Vec2::ZERO);
+        }
+
+        #[test]
+        fn timed_field_switch_falls_back() {
+            let mut field = Field::new(false);
+            assert!(field.switch(true, 1.0));
+            assert!(!field.switch(true, 1.0), "switching to the same state is no change");
+            assert_eq!(field.update(0.6), None);
+            assert!(field.enabled);
+            assert_eq!(field.update(0.6), Some(false));
+            assert!(!field.enabled);
+            assert_eq!(field.update(5.0), None);
+
+            //Without a duration it stays
+            field.switch(true, 0.0);
+            assert_eq!(field.update(5.0), None);
+            assert!(field.enabled);
+        }
+
+        //Right for two seconds, then down for one, turning while going right
+        fn path(ping_pong: bool) -> KinematicPath {
+            KinematicPath {
+                keyframes: vec![
+                    Keyframe { time: 0.0, position: vec2(0.0, 0.0), rotation: 0.0 },
+                    Keyframe { time: 2.0, position: vec2(100.0, 0.0), rotation: 1.0 },
+                    Keyframe { time: 3.0, position: vec2(100.0, 50.0), rotation: 1.0 },
+                ],
+                ping_pong,
+                spin: 0.0,
+            }
+        }
+
+        fn assert_pose(sample: (Vec2, f32, Vec2, f32), expected: (Vec2, f32, Vec2, f32)) {
+            let (position, rotation, velocity, angular_velocity) = sample;
+            assert!(position.abs_diff_eq(expected.0, 1e-3), "position {position}, expected {}", expected.0);
+            assert!((rotation - expected.1).abs() < 1e-4, "rotation {rotation}, expected {}", expected.1);
+            assert!(velocity.abs_diff_eq(expected.2, 1e-3), "velocity {velocity}, expected {}", expected.2);
+            assert!((angular_velocity - expected.3).abs() < 1e-4, "angular velocity {angular_velocity}, expected {}", expected.3);
+        }
+
+        #[test]
+        fn kinematic_path_interpolates_and_loops() {
+            let path = path(false);
+            assert_pose(path.sample(1.0), (vec2(50.0, 0.0), 0.5, vec2(50.0, 0.0), 0.5));
+            assert_pose(path.sample(2.5), (vec2(100.0, 25.0), 1.0, vec2(0.0, 50.0), 0.0));
+            //Starts over after the last keyframe
+            assert_pose(path.sample(4.0), (vec2(50.0, 0.0), 0.5, vec2(50.0, 0.0), 0.5));
+        }
+
+        #[test]
+        fn ping_pong_path_runs_back_with_reversed_velocity() {
+            let path = path(true);
+            assert_pose(path.sample(1.0), (vec2(50.0, 0.0), 0.5, vec2(50.0, 0.0), 0.5));
+            assert_pose(path.sample(3.5), (vec2(100.0, 25.0), 1.0, vec2(0.0, -50.0), 0.0));
+            assert_pose(path.sample(4.5), (vec2(75.0, 0.0), 0.75, vec2(-50.0, 0.0), -0.5));
+            //Back at the start, then forwards again
+            assert_pose(path.sample(7.0), (vec2(50.0, 0.0), 0.5, vec2(50.0, 0.0), 0.5));
+
+            //Velocity matches how far the body actually moves, both ways
+            for time in [0.5, 2.6, 3.4, 4.2, 5.9] {
+                let (before, ..) = path.sample(time - 0.01);
+                let (after, ..) = path.sample(time + 0.01);
+                let (_, _, velocity, _) = path.sample(time);
+                assert!(((after - before) / 0.02).abs_diff_eq(velocity, 1e-1), "at {time}: moved at {}, velocity {velocity}", (after - before) / 0.02);
+            }
+        }
+
+        #[test]
+        fn spinning_body_turns_in_place() {
+            let path = KinematicPath { keyframes: vec![Keyframe { time: 0.0, position: vec2(10.0, 20.0), rotation: 0.5 }], ping_pong: false, spin: 2.0 };
+            assert_pose(path.sample(1.0), (vec2(10.0, 20.0), 2.5, Vec2::ZERO, 2.0));
+            assert_pose(path.sample(4.0), (vec2(10.0, 20.0), 0.5 + 8.0 - TAU, Vec2::ZERO, 2.0));
+        }
+    }
+}
@@ -0,0 +1,1136 @@
+pub mod table {
+    use std::{fmt, fs, path::Path};
+
+    use macroquad::prelude::*;
+    use serde::Deserialize;
+
+    use crate::{collider::collider::{ColliderId, ColliderSet}, game_engine::MAX_BALLS, targets::targets::TargetBank, helper::helper::color_from_name, static_obj::static_obj::{FlipperDrive, Keyframe, Kicker, KinematicPath, Material, SensorShape, StaticBody}};
+
+    //Plain (x, y) pair, RON has no notion of Vec2
+    pub type Point = (f32, f32);
+
+    #[derive(Debug)]
+    pub enum TableError {
+        Io(std::io::Error),
+        Parse(ron::error::SpannedError),
+        //Semantic error in one entry of `bodies`
+        InvalidBody { index: usize, kind: &'static str, reason: String },
+        Invalid(String),
+    }
+
+    impl fmt::Display for TableError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TableError::Io(e) => write!(f, "could not read table file: {e}"),
+                TableError::Parse(e) => write!(f, "malformed table file: {e}"),
+                TableError::InvalidBody { index, kind, reason } => write!(f, "body #{index} ({kind}): {reason}"),
+                TableError::Invalid(reason) => write!(f, "invalid table: {reason}"),
+            }
+        }
+    }
+
+    impl std::error::Error for TableError {}
+
+    //Either a macroquad color constant name ("GRAY") or (r, g, b, a) bytes
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum ColorDescription {
+        Named(String),
+        Rgba(u8, u8, u8, u8),
+    }
+
+    impl ColorDescription {
+        fn resolve(&self) -> Option<Color> {
+            match self {
+                ColorDescription::Named(name) => color_from_name(name),
+                ColorDescription::Rgba(r, g, b, a) => Some(Color::from_rgba(*r, *g, *b, *a)),
+            }
+        }
+    }
+
+    //Either a preset name ("Rubber", "Metal", "Wood", "Plastic") or explicit coefficients
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum MaterialDescription {
+        Named(String),
+        Custom { restitution: f32, friction: f32 },
+    }
+
+    impl MaterialDescription {
+        fn resolve(&self) -> Result<Material, String> {
+            match self {
+                MaterialDescription::Named(name) => Material::from_name(name).ok_or_else(|| format!("unknown material \"{name}\"")),
+                MaterialDescription::Custom { restitution, friction } => {
+                    if !(0.0..=1.0).contains(restitution) {
+                        return Err(format!("restitution must be between 0 and 1, got {restitution}"));
+                    }
+                    if *friction < 0.0 {
+                        return Err(format!("friction must not be negative, got {friction}"));
+                    }
+                    Ok(Material { restitution: *restitution, friction: *friction })
+                },
+            }
+        }
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct BallSpawn {
+        pub position: Point,
+        pub radius: f32,
+    }
+
+    //Area of a Sensor, LayerGate or ForceZone, and the outline of a Kinematic body around its own origin
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub enum SensorShapeDescription {
+        Rectangle {
+            position: Point,
+            size: Point,
+            #[serde(default)]
+            rotation: f32,
+        },
+        Circle { position: Point, radius: f32 },
+        //Convex outline, points in either winding order
+        Polygon { points: Vec<Point> },
+    }
+
+    //Pose of a Kinematic body `time` seconds into its path
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct KeyframeDescription {
+        pub time: f32,
+        pub position: Point,
+        #[serde(default)]
+        pub rotation: f32,
+    }
+
+    //One entry per StaticBody variant, angles are in radians.
+    //Every entry may carry a unique `name` and free form `tags` used to look it up at runtime.
+    //`layer` puts it on a ramp (above 0) or under the playfield (below 0), balls only touch bodies on their own layer.
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub enum BodyDescription {
+        Rectangle {
+            position: Point,
+            size: Point,
+            #[serde(default)]
+            rotation: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            impact_force: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        Circle {
+            position: Point,
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            impact_force: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Convex outline, points in either winding order
+        Polygon {
+            points: Vec<Point>,
+            color: ColorDescription,
+            #[serde(default)]
+            impact_force: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Rail of the given radius around a line, radius 0 is a bare line
+        Segment {
+            start: Point,
+            end: Point,
+            #[serde(default)]
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Rail through every point in order
+        Polyline {
+            points: Vec<Point>,
+            #[serde(default)]
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Cubic Bezier spans sharing end points: start, control, control, end, control, control, end...
+        Bezier {
+            points: Vec<Point>,
+            #[serde(default)]
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Smooth rail passing through every point
+        CatmullRom {
+            points: Vec<Point>,
+            #[serde(default)]
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        Curve {
+            center: Point,
+            radius: f32,
+            thickness: f32,
+            angle_start: f32,
+            angle_end: f32,
+            //Rectangles used for rendering, 0 makes the curve invisible
+            segments: usize,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        Flipper {
+            origin: Point,
+            offset: Point,
+            size: Point,
+            rotation_min: f32,
+            rotation_max: f32,
+            color: ColorDescription,
+            //Input action that raises the flipper, keys are bound to actions in the settings
+            action: String,
+            //Moment of inertia around the pivot relative to the ball's mass, heavier flippers lose less speed to the ball
+            #[serde(default = "default_flipper_inertia")]
+            inertia: f32,
+            //Torque of the coil while held and of the spring pulling it back, also relative to the ball's mass
+            #[serde(default = "default_flipper_coil_torque")]
+            coil_torque: f32,
+            #[serde(default = "default_flipper_return_torque")]
+            return_torque: f32,
+            //Share of the speed kept when bouncing off the end stops
+            #[serde(default = "default_flipper_end_restitution")]
+            end_restitution: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        Spinner {
+            position: Point,
+            size: Point,
+            #[serde(default)]
+            rotation: f32,
+            //Angular deceleration in radians per second squared
+            #[serde(default = "default_spinner_friction")]
+            friction: f32,
+            //Balls slower than this bounce off the flap
+            #[serde(default = "default_spinner_min_pass_speed")]
+            min_pass_speed: f32,
+            //Flap moment of inertia relative to the ball mass, lower spins faster
+            #[serde(default = "default_spinner_inertia")]
+            inertia: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Kicks the ball away when it comes in faster than min_speed, then rests for cooldown seconds
+        PopBumper {
+            position: Point,
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default = "default_bumper_min_speed")]
+            min_speed: f32,
+            #[serde(default = "default_bumper_impulse")]
+            impulse: f32,
+            #[serde(default = "default_bumper_cooldown")]
+            cooldown: f32,
+            #[serde(default = "default_bumper_score")]
+            score: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Rubber between two posts, same kick settings as PopBumper
+        Slingshot {
+            start: Point,
+            end: Point,
+            radius: f32,
+            color: ColorDescription,
+            #[serde(default = "default_slingshot_min_speed")]
+            min_speed: f32,
+            #[serde(default = "default_slingshot_impulse")]
+            impulse: f32,
+            #[serde(default = "default_slingshot_cooldown")]
+            cooldown: f32,
+            #[serde(default = "default_slingshot_score")]
+            score: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Drops out of the way once hit, comes back up with its bank
+        DropTarget {
+            position: Point,
+            size: Point,
+            #[serde(default)]
+            rotation: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Stays in place and lights up when hit
+        StandupTarget {
+            position: Point,
+            size: Point,
+            #[serde(default)]
+            rotation: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Never touches the ball, reports it rolling in and out and lights up meanwhile
+        Sensor {
+            shape: SensorShapeDescription,
+            color: ColorDescription,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Shooter lane plunger, rotation 0 launches upwards
+        Plunger {
+            position: Point,
+            size: Point,
+            #[serde(default)]
+            rotation: f32,
+            travel: f32,
+            spring: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Moves `shape` between `keyframes` in straight lines and spins it at `spin` radians per second.
+        //The path loops, or runs back and forth with `ping_pong`. A single keyframe with a spin makes a turntable.
+        Kinematic {
+            shape: SensorShapeDescription,
+            keyframes: Vec<KeyframeDescription>,
+            #[serde(default)]
+            ping_pong: bool,
+            #[serde(default)]
+            spin: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            impact_force: f32,
+            #[serde(default)]
+            material: Option<MaterialDescription>,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Pulls balls within `radius` towards `position`, `strength` at the center fading out to the edge as (1 - distance / radius) ^ falloff.
+        //Negative strength repels.
+        Magnet {
+            position: Point,
+            radius: f32,
+            strength: f32,
+            #[serde(default = "default_magnet_falloff")]
+            falloff: f32,
+            #[serde(default = "default_field_enabled")]
+            enabled: bool,
+            color: ColorDescription,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Accelerates balls inside `shape` by `strength` towards `angle` (radians, 0 is right), for wind and kick zones
+        ForceZone {
+            shape: SensorShapeDescription,
+            angle: f32,
+            strength: f32,
+            #[serde(default = "default_field_enabled")]
+            enabled: bool,
+            color: ColorDescription,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Hole catching balls slower than `max_speed`, kicks them out after `hold_time` at `eject_angle` (radians, 0 is right)
+        Saucer {
+            position: Point,
+            radius: f32,
+            #[serde(default = "default_saucer_max_speed")]
+            max_speed: f32,
+            #[serde(default = "default_saucer_hold_time")]
+            hold_time: f32,
+            eject_angle: f32,
+            #[serde(default = "default_eject_speed")]
+            eject_speed: f32,
+            #[serde(default = "default_saucer_score")]
+            score: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Keeps every ball rolling in and serves a new one, releases them all once `capacity` are locked
+        BallLock {
+            position: Point,
+            radius: f32,
+            #[serde(default = "default_lock_capacity")]
+            capacity: u32,
+            eject_angle: f32,
+            #[serde(default = "default_eject_speed")]
+            eject_speed: f32,
+            color: ColorDescription,
+            #[serde(default)]
+            layer: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+        //Ramp entry or exit, moves balls crossing it along `direction` from layer `from` to `to` and back the other way
+        LayerGate {
+            shape: SensorShapeDescription,
+            from: i32,
+            to: i32,
+            direction: Point,
+            color: ColorDescription,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            tags: Vec<String>,
+        },
+    }
+
+    //Targets, by name, that reset together and award a bonus once all of them are hit
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct BankDescription {
+        pub targets: Vec<String>,
+        #[serde(default = "default_bank_bonus")]
+        pub bonus: f32,
+    }
+
+    //Hitting or rolling over `trigger` switches the magnets and force zones named in `fields` on.
+    //They fall back to their table state after `duration` seconds, or stay on if it is 0.
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct FieldSwitchDescription {
+        pub trigger: String,
+        pub fields: Vec<String>,
+        #[serde(default)]
+        pub duration: f32,
+    }
+
+    fn default_bank_bonus() -> f32 { 5000.0 }
+    fn default_magnet_falloff() -> f32 { 1.0 }
+    fn default_field_enabled() -> bool { true }
+    fn default_bumper_min_speed() -> f32 { 40.0 }
+    fn default_bumper_impulse() -> f32 { 250.0 }
+    fn default_bumper_cooldown() -> f32 { 0.15 }
+    fn default_bumper_score() -> f32 { 1000.0 }
+    fn default_slingshot_min_speed() -> f32 { 80.0 }
+    fn default_slingshot_impulse() -> f32 { 300.0 }
+    fn default_slingshot_cooldown() -> f32 { 0.3 }
+    fn default_slingshot_score() -> f32 { 500.0 }
+    fn default_flipper_inertia() -> f32 { FlipperDrive::default().inertia }
+    fn default_flipper_coil_torque() -> f32 { FlipperDrive::default().coil_torque }
+    fn default_flipper_return_torque() -> f32 { FlipperDrive::default().return_torque }
+    fn default_flipper_end_restitution() -> f32 { FlipperDrive::default().end_restitution }
+    fn default_saucer_max_speed() -> f32 { 150.0 }
+    fn default_saucer_hold_time() -> f32 { 1.5 }
+    fn default_saucer_score() -> f32 { 2000.0 }
+    fn default_eject_speed() -> f32 { 450.0 }
+    fn default_lock_capacity() -> u32 { 2 }
+    fn default_spinner_friction() -> f32 { 5.0 }
+    fn default_spinner_min_pass_speed() -> f32 { 150.0 }
+    fn default_spinner_inertia() -> f32 { 10.0 }
+
+    impl BodyDescription {
+        fn kind(&self) -> &'static str {
+            match self {
+                BodyDescription::Rectangle { .. } => "Rectangle",
+                BodyDescription::Circle { .. } => "Circle",
+                BodyDescription::Polygon { .. } => "Polygon",
+                BodyDescription::Segment { .. } => "Segment",
+                BodyDescription::Polyline { .. } => "Polyline",
+                BodyDescription::Bezier { .. } => "Bezier",
+                BodyDescription::CatmullRom { .. } => "CatmullRom",
+                BodyDescription::Curve { .. } => "Curve",
+                BodyDescription::Flipper { .. } => "Flipper",
+                BodyDescription::Spinner { .. } => "Spinner",
+                BodyDescription::PopBumper { .. } => "PopBumper",
+                BodyDescription::Slingshot { .. } => "Slingshot",
+                BodyDescription::DropTarget { .. } => "DropTarget",
+                BodyDescription::StandupTarget { .. } => "StandupTarget",
+                BodyDescription::Sensor { .. } => "Sensor",
+                BodyDescription::Plunger { .. } => "Plunger",
+                BodyDescription::Kinematic { .. } => "Kinematic",
+                BodyDescription::Magnet { .. } => "Magnet",
+                BodyDescription::ForceZone { .. } => "ForceZone",
+                BodyDescription::Saucer { .. } => "Saucer",
+                BodyDescription::BallLock { .. } => "BallLock",
+                BodyDescription::LayerGate { .. } => "LayerGate",
+            }
+        }
+
+        fn material(&self) -> Option<&MaterialDescription> {
+            match self {
+                BodyDescription::Rectangle { material, .. }
+                | BodyDescription::Circle { material, .. }
+                | BodyDescription::Polygon { material, .. }
+                | BodyDescription::Segment { material, .. }
+                | BodyDescription::Polyline { material, .. }
+                | BodyDescription::Bezier { material, .. }
+                | BodyDescription::CatmullRom { material, .. }
+                | BodyDescription::Curve { material, .. }
+                | BodyDescription::Flipper { material, .. }
+                | BodyDescription::Spinner { material, .. }
+                | BodyDescription::PopBumper { material, .. }
+                | BodyDescription::Slingshot { material, .. }
+                | BodyDescription::DropTarget { material, .. }
+                | BodyDescription::StandupTarget { material, .. }
+                | BodyDescription::Plunger { material, .. }
+                | BodyDescription::Kinematic { material, .. } => material.as_ref(),
+                BodyDescription::Sensor { .. }
+                | BodyDescription::Magnet { .. }
+                | BodyDescription::ForceZone { .. }
+                | BodyDescription::Saucer { .. }
+                | BodyDescription::BallLock { .. }
+                | BodyDescription::LayerGate { .. } => None,
+            }
+        }
+
+        fn identity(&self) -> (Option<&String>, &[String]) {
+            match self {
+                BodyDescription::Rectangle { name, tags, .. }
+                | BodyDescription::Circle { name, tags, .. }
+                | BodyDescription::Polygon { name, tags, .. }
+                | BodyDescription::Segment { name, tags, .. }
+                | BodyDescription::Polyline { name, tags, .. }
+                | BodyDescription::Bezier { name, tags, .. }
+                | BodyDescription::CatmullRom { name, tags, .. }
+                | BodyDescription::Curve { name, tags, .. }
+                | BodyDescription::Flipper { name, tags, .. }
+                | BodyDescription::Spinner { name, tags, .. }
+                | BodyDescription::PopBumper { name, tags, .. }
+                | BodyDescription::Slingshot { name, tags, .. }
+                | BodyDescription::DropTarget { name, tags, .. }
+                | BodyDescription::StandupTarget { name, tags, .. }
+                | BodyDescription::Sensor { name, tags, .. }
+                | BodyDescription::Plunger { name, tags, .. }
+                | BodyDescription::Kinematic { name, tags, .. }
+                | BodyDescription::Magnet { name, tags, .. }
+                | BodyDescription::ForceZone { name, tags, .. }
+                | BodyDescription::Saucer { name, tags, .. }
+                | BodyDescription::BallLock { name, tags, .. }
+                | BodyDescription::LayerGate { name, tags, .. } => (name.as_ref(), tags),
+            }
+        }
+
+        fn layer(&self) -> i32 {
+            match self {
+                BodyDescription::Rectangle { layer, .. }
+                | BodyDescription::Circle { layer, .. }
+                | BodyDescription::Polygon { layer, .. }
+                | BodyDescription::Segment { layer, .. }
+                | BodyDescription::Polyline { layer, .. }
+                | BodyDescription::Bezier { layer, .. }
+                | BodyDescription::CatmullRom { layer, .. }
+                | BodyDescription::Curve { layer, .. }
+                | BodyDescription::Flipper { layer, .. }
+                | BodyDescription::Spinner { layer, .. }
+                | BodyDescription::PopBumper { layer, .. }
+                | BodyDescription::Slingshot { layer, .. }
+                | BodyDescription::DropTarget { layer, .. }
+                | BodyDescription::StandupTarget { layer, .. }
+                | BodyDescription::Sensor { layer, .. }
+                | BodyDescription::Plunger { layer, .. }
+                | BodyDescription::Kinematic { layer, .. }
+                | BodyDescription::Magnet { layer, .. }
+                | BodyDescription::ForceZone { layer, .. }
+                | BodyDescription::Saucer { layer, .. }
+                | BodyDescription::BallLock { layer, .. } => *layer,
+                //Drawn with the upper of its two layers
+                BodyDescription::LayerGate { from, to, .. } => *from.max(to),
+            }
+        }
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct TableDescription {
+        pub ball: BallSpawn,
+        //Name of the Plunger body the launch button pulls
+        pub launcher: String,
+        //Seconds after a launch during which a drained ball is given back and auto plunged, 0 turns it off
+        #[serde(default)]
+        pub ball_save: f32,
+        //Ball is lost once it falls below this height
+        pub drain_y: f32,
+        pub bodies: Vec<BodyDescription>,
+        #[serde(default)]
+        pub banks: Vec<BankDescription>,
+        #[serde(default)]
+        pub field_switches: Vec<FieldSwitchDescription>,
+    }
+
+    //Hitting, entering or landing in a body with this tag lights the extra ball
+    pub const LIGHT_EXTRA_BALL_TAG: &str = "light_extra_ball";
+    //Bodies with this tag award the extra ball when hit or landed in while it is lit, which puts it out again
+    pub const EXTRA_BALL_TAG: &str = "extra_ball";
+
+    //Switches magnets and force zones on when its trigger is hit, see FieldSwitchDescription
+    pub struct FieldSwitch {
+        pub trigger: ColliderId,
+        pub fields: Vec<ColliderId>,
+        pub duration: f32,
+    }
+
+    //Everything GameWorld needs out of a table description
+    pub struct Table {
+        pub colliders: ColliderSet,
+        //(flipper, action that raises it)
+        pub flippers: Vec<(ColliderId, String)>,
+        pub ball_spawn: Vec2,
+        pub ball_radius: f32,
+        pub drain_y: f32,
+        pub launcher: ColliderId,
+        pub ball_save: f32,
+        pub banks: Vec<TargetBank>,
+        pub field_switches: Vec<FieldSwitch>,
+    }
+
+    impl TableDescription {
+        pub fn from_file<P: AsRef<Path>>(path: P) -> Result<TableDescription, TableError> {
+            let source = fs::read_to_string(path).map_err(TableError::Io)?;
+            TableDescription::from_ron(&source)
+        }
+
+        pub fn from_ron(source: &str) -> Result<TableDescription, TableError> {
+            //Optional fields are written without Some(...)
+            ron::Options::default()
+                .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                .from_str(source)
+                .map_err(TableError::Parse)
+        }
+
+        pub fn build(&self) -> Result<Table, TableError> {
+            if self.ball.radius <= 0.0 {
+                return Err(TableError::Invalid(format!("ball radius must be positive, got {}", self.ball.radius)));
+            }
+
+            let mut colliders = ColliderSet::default();
+            let mut flippers = Vec::new();
+
+            for (index, body) in self.bodies.iter().enumerate() {
+                let invalid = |reason: String| TableError::InvalidBody { index, kind: body.kind(), reason };
+                let color = |description: &ColorDescription| {
+                    description.resolve().ok_or_else(|| invalid(format!("unknown color {description:?}")))
+                };
+                let positive = |name: &str, value: f32| {
+                    if value > 0.0 { Ok(()) } else { Err(invalid(format!("{name} must be positive, got {value}"))) }
+                };
+                let kicker = |min_speed: f32, impulse: f32, cooldown: f32, score: f32| {
+                    if min_speed < 0.0 || impulse < 0.0 || cooldown < 0.0 {
+                        return Err(invalid(String::from("min_speed, impulse and cooldown must not be negative")));
+                    }
+                    Ok(Kicker::new(min_speed, impulse, cooldown, score))
+                };
+
+                //Shared by Polygon bodies and polygon shapes so both accept the same outlines
+                let convex_polygon = |points: &[Point]| {
+                    let points: Vec<Vec2> = points.iter().map(|p| vec2(p.0, p.1)).collect();
+                    if points.len() < 3 {
+                        return Err(invalid(format!("needs at least 3 points, got {}", points.len())));
+                    }
+                    if StaticBody::signed_area(&points).abs() < f32::EPSILON {
+                        return Err(invalid(String::from("points must enclose an area")));
+                    }
+                    if !StaticBody::is_convex(&points) {
+                        return Err(invalid(String::from("points must form a convex polygon")));
+                    }
+                    Ok(points)
+                };
+
+                let sensor_shape = |shape: &SensorShapeDescription| {
+                    Ok(match shape {
+                        SensorShapeDescription::Rectangle { position, size, rotation } => {
+                            positive("width", size.0)?;
+                            positive("height", size.1)?;
+                            SensorShape::Rectangle { position: vec2(position.0, position.1), dimensions: vec2(size.0, size.1), rotation: *rotation }
+                        },
+                        SensorShapeDescription::Circle { position, radius } => {
+                            positive("radius", *radius)?;
+                            SensorShape::Circle { position: vec2(position.0, position.1), radius: *radius }
+                        },
+                        SensorShapeDescription::Polygon { points } => {
+                            SensorShape::Polygon { points: convex_polygon(points)? }
+                        },
+                    })
+                };
+
+                let collider = match body {
+                    BodyDescription::Rectangle { position, size, rotation, color: c, impact_force, .. } => {
+                        positive("width", size.0)?;
+                        positive("height", size.1)?;
+                        StaticBody::new_rectangle(vec2(position.0, position.1), vec2(size.0, size.1), *rotation, color(c)?, *impact_force)
+                    },
+                    BodyDescription::Circle { position, radius, color: c, impact_force, .. } => {
+                        positive("radius", *radius)?;
+                        StaticBody::new_circle(vec2(position.0, position.1), *radius, color(c)?, *impact_force)
+                    },
+                    BodyDescription::Polygon { points, color: c, impact_force, .. } => {
+                        StaticBody::new_polygon(convex_polygon(points)?, color(c)?, *impact_force)
+                    },
+                    BodyDescription::Segment { start, end, radius, color: c, .. } => {
+                        if *radius < 0.0 {
+                            return Err(invalid(format!("radius must not be negative, got {radius}")));
+                        }
+                        if start == end {
+                            return Err(invalid(String::from("start and end must differ")));
+                        }
+                        StaticBody::new_segment(vec2(start.0, start.1), vec2(end.0, end.1), *radius, color(c)?)
+                    },
+                    BodyDescription::Polyline { points, radius, color: c, .. } => {
+                        if *radius < 0.0 {
+                            return Err(invalid(format!("radius must not be negative, got {radius}")));
+                        }
+                        if points.len() < 2 {
+                            return Err(invalid(format!("needs at least 2 points, got {}", points.len())));
+                        }
+                        StaticBody::new_polyline(points.iter().map(|p| vec2(p.0, p.1)).collect(), *radius, color(c)?)
+                    },
+                    BodyDescription::Bezier { points, radius, color: c, .. } => {
+                        if *radius < 0.0 {
+                            return Err(invalid(format!("radius must not be negative, got {radius}")));
+                        }
+                        if points.len() < 4 || (points.len() - 1) % 3 != 0 {
+                            return Err(invalid(format!("needs 3 points per span plus the start, got {}", points.len())));
+                        }
+                        let points: Vec<Vec2> = points.iter().map(|p| vec2(p.0, p.1)).collect();
+                        StaticBody::new_bezier(&points, *radius, color(c)?)
+                    },
+                    BodyDescription::CatmullRom { points, radius, color: c, .. } => {
+                        if *radius < 0.0 {
+                            return Err(invalid(format!("radius must not be negative, got {radius}")));
+                        }
+                        if points.len() < 2 {
+                            return Err(invalid(format!("needs at least 2 points, got {}", points.len())));
+                        }
+                        let points: Vec<Vec2> = points.iter().map(|p| vec2(p.0, p.1)).collect();
+                        StaticBody::new_catmull_rom(&points, *radius, color(c)?)
+                    },
+                    BodyDescription::Curve { center, radius, thickness, angle_start, angle_end, segments, color: c, .. } => {
+                        positive("radius", *radius)?;
+                        StaticBody::new_curve(vec2(center.0, center.1), *radius, *thickness, *angle_start, *angle_end, *segments, color(c)?)
+                    },
+                    BodyDescription::Flipper { origin, offset, size, rotation_min, rotation_max, color: c, action, inertia, coil_torque, return_torque, end_restitution, .. } => {
+                        positive("width", size.0)?;
+                        positive("height", size.1)?;
+                        if rotation_min == rotation_max {
+                            return Err(invalid(String::from("rotation_min and rotation_max must differ")));
+                        }
+                        if action.is_empty() {
+                            return Err(invalid(String::from("action must not be empty")));
+                        }
+                        positive("inertia", *inertia)?;
+                        if *coil_torque < 0.0 || *return_torque < 0.0 {
+                            return Err(invalid(String::from("coil_torque and return_torque must not be negative")));
+                        }
+                        if !(0.0..=1.0).contains(end_restitution) {
+                            return Err(invalid(format!("end_restitution must be between 0 and 1, got {end_restitution}")));
+                        }
+                        flippers.push((colliders.len(), action.clone()));
+                        let drive = FlipperDrive { inertia: *inertia, coil_torque: *coil_torque, return_torque: *return_torque, end_restitution: *end_restitution };
+                        StaticBody::new_flipper(vec2(origin.0, origin.1), vec2(offset.0, offset.1), vec2(size.0, size.1), *rotation_min, *rotation_max, color(c)?)
+                            .with_drive(drive)
+                    },
+                    BodyDescription::Spinner { position, size, rotation, friction, min_pass_speed, inertia, color: c, .. } => {
+                        positive("width", size.0)?;
+                        positive("height", size.1)?;
+                        positive("inertia", *inertia)?;
+                        if *friction < 0.0 || *min_pass_speed < 0.0 {
+                            return Err(invalid(String::from("friction and min_pass_speed must not be negative")));
+                        }
+                        StaticBody::new_spinner(vec2(position.0, position.1), vec2(size.0, size.1), *rotation, *friction, *min_pass_speed, *inertia, color(c)?)
+                    },
+                    BodyDescription::PopBumper { position, radius, color: c, min_speed, impulse, cooldown, score, .. } => {
+                        positive("radius", *radius)?;
+                        let kicker = kicker(*min_speed, *impulse, *cooldown, *score)?;
+                        StaticBody::new_pop_bumper(vec2(position.0, position.1), *radius, kicker, color(c)?)
+                    },
+                    BodyDescription::Slingshot { start, end, radius, color: c, min_speed, impulse, cooldown, score, .. } => {
+                        positive("radius", *radius)?;
+                        if start == end {
+                            return Err(invalid(String::from("start and end must differ")));
+                        }
+                        let kicker = kicker(*min_speed, *impulse, *cooldown, *score)?;
+                        StaticBody::new_slingshot(vec2(start.0, start.1), vec2(end.0, end.1), *radius, kicker, color(c)?)
+                    },
+                    BodyDescription::DropTarget { position, size, rotation, color: c, .. } => {
+                        positive("width", size.0)?;
+                        positive("height", size.1)?;
+                        StaticBody::new_drop_target(vec2(position.0, position.1), vec2(size.0, size.1), *rotation, color(c)?)
+                    },
+                    BodyDescription::StandupTarget { position, size, rotation, color: c, .. } => {
+                        positive("width", size.0)?;
+                        positive("height", size.1)?;
+                        StaticBody::new_standup_target(vec2(position.0, position.1), vec2(size.0, size.1), *rotation, color(c)?)
+                    },
+                    BodyDescription::Sensor { shape, color: c, .. } => StaticBody::new_sensor(sensor_shape(shape)?, color(c)?),
+                    BodyDescription::Plunger { position, size, rotation, travel, spring, color: c, .. } => {
+                        positive("width", size.0)?;
+                        positive("height", size.1)?;
+                        positive("travel", *travel)?;
+                        positive("spring", *spring)?;
+                        StaticBody::new_plunger(vec2(position.0, position.1), vec2(size.0, size.1), *rotation, *travel, *spring, color(c)?)
+                    },
+                    BodyDescription::Kinematic { shape, keyframes, ping_pong, spin, color: c, impact_force, .. } => {
+                        if keyframes.is_empty() {
+                            return Err(invalid(String::from("needs at least one keyframe")));
+                        }
+                        if keyframes[0].time < 0.0 {
+                            return Err(invalid(format!("keyframe times must not be negative, got {}", keyframes[0].time)));
+                        }
+                        if keyframes.windows(2).any(|k| k[1].time <= k[0].time) {
+                            return Err(invalid(String::from("keyframe times must be increasing")));
+                        }
+                        let path = KinematicPath {
+                            keyframes: keyframes.iter().map(|k| Keyframe { time: k.time, position: vec2(k.position.0, k.position.1), rotation: k.rotation }).collect(),
+                            ping_pong: *ping_pong,
+                            spin: *spin,
+                        };
+                        StaticBody::new_kinematic(sensor_shape(shape)?, path, color(c)?, *impact_force)
+                    },
+                    BodyDescription::Magnet { position, radius, strength, falloff, enabled, color: c, .. } => {
+                        positive("radius", *radius)?;
+                        if *falloff < 0.0 {
+                            return Err(invalid(format!("falloff must not be negative, got {falloff}")));
+                        }
+                        StaticBody::new_magnet(vec2(position.0, position.1), *radius, *strength, *falloff, *enabled, color(c)?)
+                    },
+                    BodyDescription::ForceZone { shape, angle, strength, enabled, color: c, .. } => {
+                        let acceleration = vec2(angle.cos(), angle.sin()) * *strength;
+                        StaticBody::new_force_zone(sensor_shape(shape)?, acceleration, *enabled, color(c)?)
+                    },
+                    BodyDescription::Saucer { position, radius, max_speed, hold_time, eject_angle, eject_speed, score, color: c, .. } => {
+                        positive("radius", *radius)?;
+                        if *max_speed < 0.0 || *hold_time < 0.0 || *eject_speed < 0.0 {
+                            return Err(invalid(String::from("max_speed, hold_time and eject_speed must not be negative")));
+                        }
+                        let eject = vec2(eject_angle.cos(), eject_angle.sin()) * *eject_speed;
+                        StaticBody::new_saucer(vec2(position.0, position.1), *radius, *max_speed, *hold_time, eject, *score, color(c)?)
+                    },
+                    BodyDescription::BallLock { position, radius, capacity, eject_angle, eject_speed, color: c, .. } => {
+                        positive("radius", *radius)?;
+                        if *capacity == 0 || *capacity as usize > MAX_BALLS {
+                            return Err(invalid(format!("capacity must be between 1 and {MAX_BALLS}, got {capacity}")));
+                        }
+                        if *eject_speed < 0.0 {
+                            return Err(invalid(format!("eject_speed must not be negative, got {eject_speed}")));
+                        }
+                        let eject = vec2(eject_angle.cos(), eject_angle.sin()) * *eject_speed;
+                        StaticBody::new_ball_lock(vec2(position.0, position.1), *radius, *capacity, eject, color(c)?)
+                    },
+                    BodyDescription::LayerGate { shape, from, to, direction, color: c, .. } => {
+                        if from == to {
+                            return Err(invalid(String::from("from and to must differ")));
+                        }
+                        if *direction == (0.0, 0.0) {
+                            return Err(invalid(String::from("direction must not be zero")));
+                        }
+                        StaticBody::new_layer_gate(sensor_shape(shape)?, *from, *to, vec2(direction.0, direction.1), color(c)?)
+                    },
+                };
+                let collider = match body.material() {
+                    Some(material) => collider.with_material(material.resolve().map_err(invalid)?),
+                    None => collider,
+                };
+                let (name, tags) = body.identity();
+                if let Some(name) = name {
+                    if name.is_empty() {
+                        return Err(invalid(String::from("name must not be empty")));
+                    }
+                    if colliders.find(name).is_some() {
+                        return Err(invalid(format!("name \"{name}\" is already used")));
+                    }
+                }
+                colliders.push(collider, name.cloned(), tags.to_vec(), body.layer());
+            }
+
+            let launcher = colliders.find(&self.launcher)
+                .ok_or_else(|| TableError::Invalid(format!("launcher: no body named \"{}\"", self.launcher)))?;
+            if !matches!(colliders.get(launcher), Some(StaticBody::Plunger { .. })) {
+                return Err(TableError::Invalid(format!("launcher: \"{}\" is not a plunger", self.launcher)));
+            }
+            if self.ball_save < 0.0 {
+                return Err(TableError::Invalid(format!("ball_save must not be negative, got {}", self.ball_save)));
+            }
+
+            let flippers = flippers.into_iter().map(|(index, action)| (colliders.id_at(index), action)).collect();
+
+            let mut banks = Vec::with_capacity(self.banks.len());
+            for (index, bank) in self.banks.iter().enumerate() {
+                let invalid = |reason: String| TableError::Invalid(format!("bank #{index}: {reason}"));
+                if bank.targets.is_empty() {
+                    return Err(invalid(String::from("needs at least one target")));
+                }
+                let mut targets = Vec::with_capacity(bank.targets.len());
+                for name in &bank.targets {
+                    let id = colliders.find(name).ok_or_else(|| invalid(format!("no body named \"{name}\"")))?;
+                    if !matches!(colliders.get(id), Some(StaticBody::DropTarget { .. } | StaticBody::StandupTarget { .. })) {
+                        return Err(invalid(format!("\"{name}\" is not a target")));
+                    }
+                    targets.push(id);
+                }
+                banks.push(TargetBank::new(targets, bank.bonus));
+            }
+
+            let mut field_switches = Vec::with_capacity(self.field_switches.len());
+            for (index, switch) in self.field_switches.iter().enumerate() {
+                let invalid = |reason: String| TableError::Invalid(format!("field switch #{index}: {reason}"));
+                let trigger = colliders.find(&switch.trigger).ok_or_else(|| invalid(format!("no body named \"{}\"", switch.trigger)))?;
+                if switch.fields.is_empty() {
+                    return Err(invalid(String::from("needs at least one field")));
+                }
+                if switch.duration < 0.0 {
+                    return Err(invalid(format!("duration must not be negative, got {}", switch.duration)));
+                }
+                let mut fields = Vec::with_capacity(switch.fields.len());
+                for name in &switch.fields {
+                    let id = colliders.find(name).ok_or_else(|| invalid(format!("no body named \"{name}\"")))?;
+                    if colliders.get(id).and_then(StaticBody::field).is_none() {
+                        return Err(invalid(format!("\"{name}\" is not a magnet or force zone")));
+                    }
+                    fields.push(id);
+                }
+                field_switches.push(FieldSwitch { trigger, fields, duration: switch.duration });
+            }
+
+            Ok(Table {
+                colliders,
+                flippers,
+                ball_spawn: vec2(self.ball.position.0, self.ball.position.1),
+                ball_radius: self.ball.radius,
+                drain_y: self.drain_y,
+                launcher,
+                ball_save: self.ball_save,
+                banks,
+                field_switches,
+            })
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use super::{TableDescription, TableError};
+
+        fn table(bodies: &str, rest: &str) -> String {
+            format!(r#"TableDescription(
+                ball: BallSpawn(position: (465.0, 600.0), radius: 10.0),
+                launcher: "plunger",
+                drain_y: 1000.0,
+                bodies: [
+                    Plunger(position: (465.0, 615.0), size: (28.0, 10.0), travel: 30.0, spring: 750.0, color: "GRAY", name: "plunger"),
+                    {bodies}
+                ],
+                {rest}
+            )"#)
+        }
+
+        fn build(source: &str) -> Result<(), TableError> {
+            TableDescription::from_ron(source)?.build().map(|_| ())
+        }
+
+        fn invalid(source: &str, part: &str) {
+            match build(source) {
+                Err(TableError::Invalid(reason)) => assert!(reason.contains(part), "{reason}"),
+                Err(error) => panic!("expected Invalid, got {error}"),
+                Ok(()) => panic!("expected Invalid, table loaded"),
+            }
+        }
+
+        fn build_with_polygon(points: &str) -> Result<(), TableError> {
+            let source = format!(r#"TableDescription(
+                ball: BallSpawn(position: (465.0, 600.0), radius: 10.0),
+                launcher: "plunger",
+                drain_y: 1000.0,
+                bodies: [
+                    Plunger(position: (465.0, 615.0), size: (28.0, 10.0), travel: 30.0, spring: 750.0, color: "GRAY", name: "plunger"),
+                    Polygon(points: {points}, color: "GRAY"),
+                    Sensor(shape: Polygon(points: {points}), color: "GRAY"),
+                ],
+            )"#);
+            TableDescription::from_ron(&source)?.build().map(|_| ())
+        }
+
+        #[test]
+        fn convex_polygon_loads() {
+            assert!(build_with_polygon("[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]").is_ok());
+        }
+
+        #[test]
+        fn non_convex_polygons_are_rejected() {
+            for points in ["[(0.0, 0.0), (10.0, 5.0), (0.0, 10.0), (4.0, 5.0)]", "[(10.0, 0.0), (-8.1, 5.9), (3.1, -9.5), (3.1, 9.5), (-8.1, -5.9)]"] {
+                let error = build_with_polygon(points).expect_err(points);
+                assert!(matches!(error, TableError::InvalidBody { index: 1, kind: "Polygon", .. }), "{points}: {error}");
+            }
+        }
+
+        #[test]
+        fn default_table_loads() {
+            let table = TableDescription::from_file(crate::game_engine::DEFAULT_TABLE).expect("default table parses");
+            assert!(table.build().is_ok());
+        }
+
+        #[test]
+        fn missing_file_is_an_io_error() {
+            let error = TableDescription::from_file("tables/does_not_exist.ron").expect_err("file is missing");
+            assert!(matches!(error, TableError::Io(_)), "{error}");
+        }
+
+        #[test]
+        fn malformed_ron_and_unknown_fields_are_parse_errors() {
+            for source in ["TableDescription(", &table("", "bonus_multiplier: 2.0,")] {
+                let error = TableDescription::from_ron(source).expect_err(source);
+                assert!(matches!(error, TableError::Parse(_)), "{error}");
+            }
+        }
+
+        #[test]
+        fn launcher_must_be_a_plunger() {
+            invalid(&table("", "").replace(r#"launcher: "plunger""#, r#"launcher: "missing""#), "no body named \"missing\"");
+            let source = table(r#"Circle(position: (100.0, 100.0), radius: 10.0, color: "GRAY", name: "post"),"#, "");
+            invalid(&source.replace(r#"launcher: "plunger""#, r#"launcher: "post""#), "is not a plunger");
+        }
+
+        #[test]
+        fn negative_ball_save_is_rejected() {
+            invalid(&table("", "ball_save: -1.0,"), "ball_save");
+        }
+
+        #[test]
+        fn ball_radius_must_be_positive() {
+            for radius in ["0.0", "-5.0"] {
+                invalid(&table("", "").replace("radius: 10.0", &format!("radius: {radius}")), "ball radius");
+            }
+        }
+
+        #[test]
+        fn banks_and_field_switches_must_name_existing_bodies() {
+            invalid(&table("", r#"banks: [(targets: ["nowhere"], bonus: 100.0)],"#), "bank #0: no body named \"nowhere\"");
+            invalid(&table("", r#"banks: [(targets: ["plunger"], bonus: 100.0)],"#), "is not a target");
+            invalid(&table("", r#"field_switches: [(trigger: "nowhere", fields: ["plunger"], duration: 1.0)],"#), "field switch #0: no body named \"nowhere\"");
+            invalid(&table("", r#"field_switches: [(trigger: "plunger", fields: ["plunger"], duration: 1.0)],"#), "is not a magnet or force zone");
+        }
+
+        #[test]
+        fn bad_color_and_duplicate_names_point_at_the_body() {
+            let error = build(&table(r#"Circle(position: (100.0, 100.0), radius: 10.0, color: "NOT_A_COLOR"),"#, "")).expect_err("unknown color");
+            assert!(matches!(error, TableError::InvalidBody { index: 1, kind: "Circle", .. }), "{error}");
+
+            let error = build(&table(r#"Circle(position: (100.0, 100.0), radius: 10.0, color: "GRAY", name: "plunger"),"#, "")).expect_err("duplicate name");
+            assert!(matches!(error, TableError::InvalidBody { index: 1, kind: "Circle", .. }), "{error}");
+        }
+    }
+}
@@ -0,0 +1,49 @@
+pub mod targets {
+    use crate::collider::collider::{ColliderId, ColliderSet};
+
+    //Drop and standup targets that reset together once every one of them has been hit
+    pub struct TargetBank {
+        pub targets: Vec<ColliderId>,
+        //Points for completing the bank
+        pub bonus: f32,
+        //Seconds until the completed bank comes back up
+        reset_timer: Option<f32>,
+    }
+
+    impl TargetBank {
+        pub fn new(targets: Vec<ColliderId>, bonus: f32) -> TargetBank {
+            TargetBank { targets, bonus, reset_timer: None }
+        }
+
+        //True while waiting to reset, a completed bank only counts once
+        pub fn is_resetting(&self) -> bool {
+            self.reset_timer.is_some()
+        }
+
+        pub fn is_complete(&self, colliders: &ColliderSet) -> bool {
+            self.targets.iter().all(|id| colliders.get(*id).is_some_and(|t| t.is_target_hit()))
+        }
+
+        pub fn schedule_reset(&mut self, delay: f32) {
+            self.reset_timer = Some(delay);
+        }
+
+        //Counts down a scheduled reset and raises the targets once it runs out
+        pub fn update(&mut self, dt: f32, colliders: &mut ColliderSet) {
+            let Some(timer) = &mut self.reset_timer else { return };
+            *timer -= dt;
+            if *timer <= 0.0 {
+                self.reset(colliders);
+            }
+        }
+
+        pub fn reset(&mut self, colliders: &mut ColliderSet) {
+            self.reset_timer = None;
+            for id in &self.targets {
+                if let Some(target) = colliders.get_mut(*id) {
+                    target.reset_target();
+                }
+            }
+        }
+    }
+}